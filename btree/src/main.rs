@@ -1,5 +1,6 @@
 mod node;
 use node::*;
+use trace::JsonLines;
 
 #[macro_export]
 macro_rules! insert_print {
@@ -82,7 +83,28 @@ macro_rules! del_char {
     };
 }
 
+// The book's insert sequence and Figure 18.8 deletions with t = 3, every
+// split, borrow and merge written to stdout as a JSON line.
+fn trace_book_case() {
+    let mut a: Btree<char, char> = Btree::new(3);
+    let mut out = JsonLines::new(std::io::stdout().lock());
+    for c in "FSQKCLHTVWMRNPABXYDZE".chars() {
+        a.insert_traced(c, c, &mut out);
+    }
+    for c in "FMGDB".chars() {
+        a.delete_traced(&c, &mut out);
+    }
+    if let Err(e) = out.finish() {
+        eprintln!("trace: {}", e);
+    }
+}
+
 fn main() {
+    if std::env::args().any(|arg| arg == "--trace") {
+        trace_book_case();
+        return;
+    }
+
     let mut a: Btree<u8, u8> = Btree::new(2);
 
     // /* Split test */
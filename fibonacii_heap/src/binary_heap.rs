@@ -0,0 +1,112 @@
+use crate::AddressableHeap;
+
+// Binary min-heap that remembers where every item sits, so decrease_key can
+// sift it up in O(log n).
+pub struct IndexedBinaryHeap<K> {
+    heap: Vec<(K, usize)>,
+    position: Vec<Option<usize>>,
+}
+
+impl<K: Ord> IndexedBinaryHeap<K> {
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.position[self.heap[a].1] = Some(a);
+        self.position[self.heap[b].1] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].0 >= self.heap[parent].0 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if l < self.heap.len() && self.heap[l].0 < self.heap[smallest].0 {
+                smallest = l;
+            }
+            if r < self.heap.len() && self.heap[r].0 < self.heap[smallest].0 {
+                smallest = r;
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K: Ord> AddressableHeap<K> for IndexedBinaryHeap<K> {
+    fn with_capacity(n: usize) -> Self {
+        IndexedBinaryHeap {
+            heap: Vec::with_capacity(n),
+            position: vec![None; n],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        self.position.get(item).is_some_and(|p| p.is_some())
+    }
+
+    fn key(&self, item: usize) -> Option<&K> {
+        let pos = (*self.position.get(item)?)?;
+        Some(&self.heap[pos].0)
+    }
+
+    fn push(&mut self, item: usize, key: K) {
+        if item >= self.position.len() {
+            self.position.resize(item + 1, None);
+        }
+        assert!(
+            self.position[item].is_none(),
+            "item {} already queued",
+            item
+        );
+        self.heap.push((key, item));
+        self.position[item] = Some(self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    fn peek(&self) -> Option<(usize, &K)> {
+        self.heap.first().map(|(k, item)| (*item, k))
+    }
+
+    fn pop(&mut self) -> Option<(usize, K)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, item) = self.heap.pop().unwrap();
+        self.position[item] = None;
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((item, key))
+    }
+
+    fn decrease_key(&mut self, item: usize, key: K) -> bool {
+        let pos = match self.position.get(item) {
+            Some(Some(pos)) => *pos,
+            _ => return false,
+        };
+        if key > self.heap[pos].0 {
+            return false;
+        }
+        self.heap[pos].0 = key;
+        self.sift_up(pos);
+        true
+    }
+}
@@ -0,0 +1,231 @@
+use crate::AddressableHeap;
+
+// Fibonacci heap from CLRS chapter 19. Nodes live in a vector indexed by item,
+// and the circular sibling lists are kept as indices into it.
+struct Node<K> {
+    key: K,
+    parent: Option<usize>,
+    child: Option<usize>,
+    left: usize,
+    right: usize,
+    degree: usize,
+    mark: bool,
+}
+
+pub struct FibonacciHeap<K> {
+    nodes: Vec<Option<Node<K>>>,
+    min: Option<usize>,
+    n: usize,
+}
+
+impl<K: Ord> FibonacciHeap<K> {
+    fn node(&self, x: usize) -> &Node<K> {
+        self.nodes[x].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, x: usize) -> &mut Node<K> {
+        self.nodes[x].as_mut().unwrap()
+    }
+
+    // Puts the lone node `x` to the right of `at` in `at`'s sibling list.
+    fn splice(&mut self, at: usize, x: usize) {
+        let right = self.node(at).right;
+        self.node_mut(x).left = at;
+        self.node_mut(x).right = right;
+        self.node_mut(at).right = x;
+        self.node_mut(right).left = x;
+    }
+
+    // Takes `x` out of its sibling list, leaving it as a list of its own.
+    fn unlink(&mut self, x: usize) {
+        let (left, right) = (self.node(x).left, self.node(x).right);
+        self.node_mut(left).right = right;
+        self.node_mut(right).left = left;
+        self.node_mut(x).left = x;
+        self.node_mut(x).right = x;
+    }
+
+    fn add_root(&mut self, x: usize) {
+        self.node_mut(x).parent = None;
+        match self.min {
+            Some(m) => {
+                self.splice(m, x);
+                if self.node(x).key < self.node(m).key {
+                    self.min = Some(x);
+                }
+            }
+            None => self.min = Some(x),
+        }
+    }
+
+    fn siblings(&self, start: usize) -> Vec<usize> {
+        let mut list = vec![start];
+        let mut x = self.node(start).right;
+        while x != start {
+            list.push(x);
+            x = self.node(x).right;
+        }
+        list
+    }
+
+    // Makes root `y` a child of root `x`.
+    fn link(&mut self, y: usize, x: usize) {
+        self.unlink(y);
+        match self.node(x).child {
+            Some(c) => self.splice(c, y),
+            None => self.node_mut(x).child = Some(y),
+        }
+        self.node_mut(y).parent = Some(x);
+        self.node_mut(y).mark = false;
+        self.node_mut(x).degree += 1;
+    }
+
+    fn consolidate(&mut self) {
+        let roots = match self.min {
+            Some(m) => self.siblings(m),
+            None => return,
+        };
+
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+        for w in roots {
+            let mut x = w;
+            let mut d = self.node(x).degree;
+            loop {
+                if d >= by_degree.len() {
+                    by_degree.resize(d + 1, None);
+                }
+                let y = match by_degree[d].take() {
+                    Some(y) => y,
+                    None => break,
+                };
+                let (x_min, y_min) = if self.node(y).key < self.node(x).key {
+                    (y, x)
+                } else {
+                    (x, y)
+                };
+                self.link(y_min, x_min);
+                x = x_min;
+                d += 1;
+            }
+            by_degree[d] = Some(x);
+        }
+
+        self.min = None;
+        for x in by_degree.into_iter().flatten() {
+            self.unlink(x);
+            self.add_root(x);
+        }
+    }
+
+    fn cut(&mut self, x: usize, y: usize) {
+        if self.node(y).child == Some(x) {
+            let right = self.node(x).right;
+            self.node_mut(y).child = if right == x { None } else { Some(right) };
+        }
+        self.unlink(x);
+        self.node_mut(y).degree -= 1;
+        self.node_mut(x).mark = false;
+        self.add_root(x);
+    }
+
+    fn cascading_cut(&mut self, mut y: usize) {
+        while let Some(z) = self.node(y).parent {
+            if !self.node(y).mark {
+                self.node_mut(y).mark = true;
+                return;
+            }
+            self.cut(y, z);
+            y = z;
+        }
+    }
+}
+
+impl<K: Ord> AddressableHeap<K> for FibonacciHeap<K> {
+    fn with_capacity(n: usize) -> Self {
+        let mut nodes = Vec::with_capacity(n);
+        nodes.resize_with(n, || None);
+        FibonacciHeap {
+            nodes,
+            min: None,
+            n: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.n
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        self.nodes.get(item).is_some_and(|n| n.is_some())
+    }
+
+    fn key(&self, item: usize) -> Option<&K> {
+        self.nodes.get(item)?.as_ref().map(|n| &n.key)
+    }
+
+    fn push(&mut self, item: usize, key: K) {
+        if item >= self.nodes.len() {
+            self.nodes.resize_with(item + 1, || None);
+        }
+        assert!(self.nodes[item].is_none(), "item {} already queued", item);
+        self.nodes[item] = Some(Node {
+            key,
+            parent: None,
+            child: None,
+            left: item,
+            right: item,
+            degree: 0,
+            mark: false,
+        });
+        self.add_root(item);
+        self.n += 1;
+    }
+
+    fn peek(&self) -> Option<(usize, &K)> {
+        self.min.map(|m| (m, &self.node(m).key))
+    }
+
+    fn pop(&mut self) -> Option<(usize, K)> {
+        let z = self.min?;
+        if let Some(c) = self.node(z).child {
+            for x in self.siblings(c) {
+                self.unlink(x);
+                self.add_root(x);
+            }
+        }
+
+        let right = self.node(z).right;
+        self.unlink(z);
+        if right == z {
+            self.min = None;
+        } else {
+            self.min = Some(right);
+            self.consolidate();
+        }
+
+        self.n -= 1;
+        let node = self.nodes[z].take().unwrap();
+        Some((z, node.key))
+    }
+
+    fn decrease_key(&mut self, item: usize, key: K) -> bool {
+        match self.nodes.get(item) {
+            Some(Some(node)) if key <= node.key => {}
+            _ => return false,
+        }
+        self.node_mut(item).key = key;
+
+        if let Some(y) = self.node(item).parent {
+            if self.node(item).key < self.node(y).key {
+                self.cut(item, y);
+                self.cascading_cut(y);
+            }
+        }
+        if let Some(m) = self.min {
+            if self.node(item).key < self.node(m).key {
+                self.min = Some(item);
+            }
+        }
+        true
+    }
+}
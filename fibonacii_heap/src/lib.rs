@@ -0,0 +1,92 @@
+mod binary_heap;
+mod fibonacci_heap;
+
+pub use binary_heap::*;
+pub use fibonacci_heap::*;
+
+// A min-priority queue over the items 0..capacity whose keys can be lowered
+// in place, as Prim and Dijkstra need in CLRS 23.2 and 24.3.
+pub trait AddressableHeap<K: Ord> {
+    fn with_capacity(n: usize) -> Self;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains(&self, item: usize) -> bool;
+
+    fn key(&self, item: usize) -> Option<&K>;
+
+    // Panics if `item` is already queued.
+    fn push(&mut self, item: usize, key: K);
+
+    fn peek(&self) -> Option<(usize, &K)>;
+
+    fn pop(&mut self) -> Option<(usize, K)>;
+
+    // Returns false, leaving the heap unchanged, if `item` isn't queued or
+    // `key` is larger than its current key.
+    fn decrease_key(&mut self, item: usize, key: K) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Random pushes, decrease_keys and pops checked against a plain vector.
+    fn check<H: AddressableHeap<u32>>() {
+        let mut state = 7u64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as u32
+        };
+
+        let n = 200;
+        let mut heap = H::with_capacity(n);
+        let mut keys: Vec<Option<u32>> = vec![None; n];
+        for _ in 0..5000 {
+            let item = next() as usize % n;
+            match next() % 3 {
+                0 if keys[item].is_none() => {
+                    let key = next() % 1000;
+                    heap.push(item, key);
+                    keys[item] = Some(key);
+                }
+                1 => {
+                    let key = next() % 1000;
+                    let lowered = keys[item].is_some_and(|k| key <= k);
+                    assert_eq!(heap.decrease_key(item, key), lowered);
+                    if lowered {
+                        keys[item] = Some(key);
+                    }
+                }
+                _ => {
+                    let expected = keys.iter().flatten().min().copied();
+                    match heap.pop() {
+                        Some((item, key)) => {
+                            assert_eq!(Some(key), expected);
+                            assert_eq!(keys[item], Some(key));
+                            keys[item] = None;
+                        }
+                        None => assert_eq!(expected, None),
+                    }
+                }
+            }
+            assert_eq!(heap.len(), keys.iter().flatten().count());
+        }
+    }
+
+    #[test]
+    fn binary_heap_matches_model() {
+        check::<IndexedBinaryHeap<u32>>();
+    }
+
+    #[test]
+    fn fibonacci_heap_matches_model() {
+        check::<FibonacciHeap<u32>>();
+    }
+}
@@ -1,3 +1,15 @@
+use fibonacii_heap::*;
+
 fn main() {
-    println!("Hello, world!");
+    let mut heap = FibonacciHeap::with_capacity(8);
+    for (item, key) in [(0, 7), (1, 3), (2, 9), (3, 1), (4, 5)] {
+        heap.push(item, key);
+    }
+    println!("min {:?}", heap.pop());
+
+    heap.decrease_key(2, 0);
+    while let Some((item, key)) = heap.pop() {
+        print!("{} ({}) ", item, key);
+    }
+    println!();
 }
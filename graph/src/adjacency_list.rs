@@ -1,186 +1,486 @@
-use std::cell::RefCell;
-use std::rc::Rc;
-
-type Eptr<T> = Rc<RefCell<Edge<T>>>;
-type EList<T> = Vec<Eptr<T>>;
-type Vptr<T> = Rc<RefCell<Vertex<T>>>;
-type Vlist<T> = Vec<Rc<RefCell<Vertex<T>>>>;
-
-pub struct Edge<T> {
-    data: T,
-    from: Rc<RefCell<Vertex<T>>>,
-    to: Rc<RefCell<Vertex<T>>>,
-}
-
-impl<T> Edge<T> {
-    pub fn new(data: T, from: Vptr<T>, to: Vptr<T>) -> Self {
-        Edge { data, from, to }
-    }
-
-    pub fn new_eptr(data: T, from: Vptr<T>, to: Vptr<T>) -> Eptr<T> {
-        Rc::new(RefCell::new(Edge::<T>::new(data, from, to)))
-    }
-}
-
-// pub struct Vertex<T> {
-//     idx: usize,
-//     elist: EList<T>,
-// }
-
-// impl<T> Vertex<T> {
-//     pub fn new(idx: usize) -> Self {
-//         Vertex {
-//             idx,
-//             elist: Vec::<Rc<RefCell<Edge<T>>>>::new(),
-//         }
-//     }
-
-//     pub fn new_vptr(idx: usize) -> Vptr<T> {
-//         Rc::new(RefCell::new(Vertex::<T>::new(idx)))
-//     }
-
-//     pub fn push_edge(&mut self, e: Eptr<T>) {
-//         self.elist.push(e);
-//     }
-// }
-
-pub struct AdjGraph<T> {
-    vertexs: Vlist<T>,
-}
-
-impl<T> AdjGraph<T> {
-    pub fn get_vertex(&self, idx: usize) -> Vptr<T> {
-        Rc::clone(&self.vertexs[idx])
-    }
-
-    pub fn iter(&self) -> std::slice::Iter<'_, std::rc::Rc<std::cell::RefCell<Vertex<T>>>> {
-        self.vertexs.iter()
-    }
-
-    // pub fn push_edge(&mut self, from_idx: usize, e: Eptr<T>) {
-    //     self.vlist[from_idx].borrow_mut().push_edge(e);
-    // }
-
-    // pub fn new(v: usize) -> Self {
-    //     let mut vlist = Vlist::<T>::new();
-    //     for i in 1..=v {
-    //         vlist.push(Vertex::<T>::new_vptr(i));
-    //     }
-
-    //     AdjGraph { vlist }
-    // }
-
-    // pub fn build(v: usize, edges: Vec<(usize, usize)>) -> AdjGraph<()> {
-    //     let mut g = AdjGraph::<()>::new(v);
-
-    //     for (from, to) in edges {
-    //         let e = Edge::<()>::new_eptr((), g.get_vertex(from), g.get_vertex(to));
-    //         g.push_edge(from, e);
-    //     }
-
-    //     g
-    // }
-}
-
-pub struct Vertex<T> {
-    idx: usize,
-    visited: u8,
-    vlist: Vlist<T>,
-    info: T,
-}
-
-impl<T> Vertex<T> {
-    pub fn new(idx: usize, info: T) -> Self {
-        Vertex {
-            idx,
-            visited: 0,
-            vlist: Vec::<Vptr<T>>::new(),
-            info,
-        }
-    }
-
-    pub fn new_vptr(idx: usize, info: T) -> Vptr<T> {
-        Rc::new(RefCell::new(Vertex::<T>::new(idx, info)))
-    }
-
-    pub fn link(&mut self, v: Vptr<T>) {
-        self.vlist.push(v);
-    }
-}
-
-enum Color {
-    White,
-    Gray,
-    Black,
-}
-struct VInfo<P> {
-    color: Color,
-    discover_time: u8,
-    finish_time: u8,
-    depth: u8,
-    ancestor: Option<P>,
-}
-
-impl<P> VInfo<P> {
-    pub fn init() -> VInfo<P> {
-        VInfo {
-            color: Color::White,
-            discover_time: 0,
-            finish_time: 0,
-            depth: u8::MAX,
-            ancestor: None,
-        }
-    }
-}
-
-type NmVertex = Vertex<()>;
-type NmVptr = Rc<RefCell<NmVertex>>;
-
-impl NmVertex {
-    pub fn new_nmv_ptr(idx: usize) -> NmVptr {
-        Vertex::<()>::new_vptr(idx, ())
-    }
-}
-
-type UndiGraph = AdjGraph<()>;
-impl UndiGraph {
-    pub fn new_undigraph(v: usize) -> UndiGraph {
-        let mut vertexs = Vec::<NmVptr>::new();
-        UndiGraph { vertexs }
-    }
-
-    pub fn build_undigraph(v: usize, edges: Vec<(usize, usize)>) -> UndiGraph {
-        let mut g = UndiGraph::new_undigraph(v);
-
-        for (from, to) in edges {
-            let mut v_from = g.get_vertex(from);
-            v_from.borrow_mut().link(g.get_vertex(to));
-
-            let mut v_to = g.get_vertex(to);
-            v_to.borrow_mut().link(v_from);
-        }
-
-        g
-    }
-
-    // pub fn dfs(&mut self) {
-
-    //     fn dfs_visit(&mut self, u: NmVptr) {
-
-    //     }
-    // }
-}
-
-impl std::fmt::Display for UndiGraph {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // write!(f, "({}, {})", self.x, self.y)
-        for v in self.vertexs.iter() {
-            write!(f, "{}: ", v.borrow().idx)?;
-            for vv in v.borrow().vlist.iter() {
-                write!(f, "{} ", vv.borrow().idx)?;
-            }
-        }
-
-        write!(f, " ")
-    }
-}
+use crate::dfs::{DfsEvent, DfsEvents, EdgeKind};
+use crate::search;
+use crate::traits::{Color, Graph, GraphMut};
+use crate::weight::Weight;
+
+// Adjacency-list graph whose vertices carry a payload `T` (a name,
+// coordinates, ...) and whose edges carry a payload `E`. Edges live in one
+// list and every vertex keeps the ids of the edges touching it, so an
+// undirected edge is stored only once.
+
+// Edge payloads `add_edge` accepts. Any payload is valid by default; a
+// weight is valid unless it is NaN. Implement it (usually empty) for a
+// payload of your own.
+pub trait EdgeData {
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+impl EdgeData for () {}
+
+impl<W: Weight> EdgeData for W {
+    fn is_valid(&self) -> bool {
+        Weight::is_valid(*self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Edge<E> {
+    data: E,
+    from: usize,
+    to: usize,
+}
+
+impl<E> Edge<E> {
+    pub fn new(data: E, from: usize, to: usize) -> Self {
+        Edge { data, from, to }
+    }
+
+    pub fn data(&self) -> &E {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut E {
+        &mut self.data
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    // The far end when the edge is walked from `v`.
+    pub fn other(&self, v: usize) -> usize {
+        if self.from == v {
+            self.to
+        } else {
+            self.from
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Vertex<T> {
+    idx: usize,
+    info: T,
+    elist: Vec<usize>,
+}
+
+impl<T> Vertex<T> {
+    pub fn new(idx: usize, info: T) -> Self {
+        Vertex {
+            idx,
+            info,
+            elist: Vec::new(),
+        }
+    }
+
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn info(&self) -> &T {
+        &self.info
+    }
+
+    pub fn info_mut(&mut self) -> &mut T {
+        &mut self.info
+    }
+
+    pub fn degree(&self) -> usize {
+        self.elist.len()
+    }
+
+    fn link(&mut self, e: usize) {
+        self.elist.push(e);
+    }
+}
+
+// What a traversal learned about one vertex.
+#[derive(Debug, Clone)]
+pub struct VInfo<P = usize> {
+    color: Color,
+    discover_time: u32,
+    finish_time: u32,
+    depth: u32,
+    ancestor: Option<P>,
+}
+
+impl<P: Copy> VInfo<P> {
+    pub fn init() -> VInfo<P> {
+        VInfo {
+            color: Color::White,
+            discover_time: 0,
+            finish_time: 0,
+            depth: u32::MAX,
+            ancestor: None,
+        }
+    }
+
+    pub fn is_visited(&self) -> bool {
+        self.color != Color::White
+    }
+
+    pub fn discover_time(&self) -> u32 {
+        self.discover_time
+    }
+
+    pub fn finish_time(&self) -> u32 {
+        self.finish_time
+    }
+
+    // u32::MAX when the vertex wasn't reached.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn ancestor(&self) -> Option<P> {
+        self.ancestor
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdjGraph<T, E = ()> {
+    vertexs: Vec<Vertex<T>>,
+    edges: Vec<Edge<E>>,
+    directed: bool,
+}
+
+impl<T, E> AdjGraph<T, E> {
+    pub fn new(directed: bool) -> Self {
+        AdjGraph {
+            vertexs: Vec::new(),
+            edges: Vec::new(),
+            directed,
+        }
+    }
+
+    // One vertex per payload, numbered in order.
+    pub fn from_infos(directed: bool, infos: Vec<T>) -> Self {
+        let mut g = AdjGraph::new(directed);
+        for info in infos {
+            g.add_vertex(info);
+        }
+        g
+    }
+
+    #[allow(non_snake_case)]
+    pub fn E(&self) -> usize {
+        self.edges.len()
+    }
+    #[allow(non_snake_case)]
+    pub fn V(&self) -> usize {
+        self.vertexs.len()
+    }
+
+    pub fn directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn add_vertex(&mut self, info: T) -> usize {
+        let idx = self.V();
+        self.vertexs.push(Vertex::new(idx, info));
+        idx
+    }
+
+    // Out-of-range endpoints and invalid payloads, such as a NaN weight,
+    // are rejected.
+    pub fn add_edge(&mut self, from_idx: usize, to_idx: usize, data: E) -> bool
+    where
+        E: EdgeData,
+    {
+        if from_idx >= self.V() || to_idx >= self.V() || !data.is_valid() {
+            return false;
+        }
+
+        let id = self.edges.len();
+        self.edges.push(Edge::new(data, from_idx, to_idx));
+        self.vertexs[from_idx].link(id);
+        if !self.directed && from_idx != to_idx {
+            self.vertexs[to_idx].link(id);
+        }
+        true
+    }
+
+    pub fn get_vertex(&self, idx: usize) -> &Vertex<T> {
+        &self.vertexs[idx]
+    }
+
+    pub fn get_vertex_mut(&mut self, idx: usize) -> &mut Vertex<T> {
+        &mut self.vertexs[idx]
+    }
+
+    pub fn info(&self, idx: usize) -> &T {
+        &self.vertexs[idx].info
+    }
+
+    pub fn info_mut(&mut self, idx: usize) -> &mut T {
+        &mut self.vertexs[idx].info
+    }
+
+    pub fn edge(&self, id: usize) -> &Edge<E> {
+        &self.edges[id]
+    }
+
+    pub fn edge_mut(&mut self, id: usize) -> &mut Edge<E> {
+        &mut self.edges[id]
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Vertex<T>> {
+        self.vertexs.iter()
+    }
+
+    pub fn iter_edges(&self) -> std::slice::Iter<'_, Edge<E>> {
+        self.edges.iter()
+    }
+
+    // Edges leaving `v`; in an undirected graph every edge touching it.
+    pub fn out_edges(&self, v: usize) -> impl Iterator<Item = &Edge<E>> + '_ {
+        self.vertexs[v].elist.iter().map(move |&id| &self.edges[id])
+    }
+
+    fn for_each_incident<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, &E),
+    {
+        for e in self.out_edges(v) {
+            f(e.other(v), &e.data);
+        }
+    }
+}
+
+impl<T: Default, E> AdjGraph<T, E> {
+    pub fn new_digraph(v: usize) -> Self {
+        AdjGraph::from_infos(true, (0..v).map(|_| T::default()).collect())
+    }
+
+    pub fn new_undigraph(v: usize) -> Self {
+        AdjGraph::from_infos(false, (0..v).map(|_| T::default()).collect())
+    }
+}
+
+impl<T: Default> AdjGraph<T, ()> {
+    pub fn build_digraph(v: usize, edges: Vec<(usize, usize)>) -> Self {
+        let mut g = AdjGraph::new_digraph(v);
+        for (from, to) in edges {
+            g.add_edge(from, to, ());
+        }
+        g
+    }
+
+    pub fn build_undigraph(v: usize, edges: Vec<(usize, usize)>) -> Self {
+        let mut g = AdjGraph::new_undigraph(v);
+        for (from, to) in edges {
+            g.add_edge(from, to, ());
+        }
+        g
+    }
+}
+
+// Edge payloads that are weights are used as such.
+impl<T, E: Weight> Graph for AdjGraph<T, E> {
+    type Weight = E;
+
+    fn vertex_count(&self) -> usize {
+        self.V()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.E()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, E),
+    {
+        self.for_each_incident(v, |u, w| f(u, *w));
+    }
+}
+
+impl<T: Default, E: Weight> GraphMut for AdjGraph<T, E> {
+    fn push_vertex(&mut self) -> usize {
+        AdjGraph::add_vertex(self, T::default())
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: E) -> bool {
+        AdjGraph::add_edge(self, from, to, weight)
+    }
+}
+
+// Unlabelled edges all have length one.
+impl<T> Graph for AdjGraph<T, ()> {
+    type Weight = u32;
+
+    fn vertex_count(&self) -> usize {
+        self.V()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.E()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, u32),
+    {
+        self.for_each_incident(v, |u, _| f(u, 1));
+    }
+}
+
+impl<T, E> AdjGraph<T, E>
+where
+    Self: Graph,
+{
+    // Depth and ancestor of every vertex reached from `v_idx`.
+    pub fn breadth_first_search(&self, v_idx: usize) -> Vec<VInfo> {
+        let (depth, ancestor) = search::breadth_first_search(self, v_idx);
+        depth
+            .into_iter()
+            .zip(ancestor)
+            .map(|(d, a)| {
+                let mut info = VInfo::init();
+                if d != u32::MAX {
+                    info.color = Color::Black;
+                    info.depth = d;
+                    info.ancestor = a;
+                }
+                info
+            })
+            .collect()
+    }
+
+    // Discover and finish times over the whole graph; `depth` is the depth
+    // in the DFS forest.
+    pub fn depth_first_search(&self) -> Vec<VInfo> {
+        let mut res = vec![VInfo::init(); self.V()];
+        let mut time = 0;
+        for event in self.dfs_events() {
+            match event {
+                DfsEvent::Discover(v) => {
+                    time += 1;
+                    res[v].discover_time = time;
+                    res[v].color = Color::Gray;
+                    if res[v].ancestor.is_none() {
+                        res[v].depth = 0;
+                    }
+                }
+                DfsEvent::Finish(v) => {
+                    time += 1;
+                    res[v].finish_time = time;
+                    res[v].color = Color::Black;
+                }
+                DfsEvent::Edge(u, v, EdgeKind::Tree) => {
+                    res[v].ancestor = Some(u);
+                    res[v].depth = res[u].depth + 1;
+                }
+                DfsEvent::Edge(..) => {}
+            }
+        }
+        res
+    }
+
+    pub fn dfs_events(&self) -> DfsEvents {
+        search::dfs_events(self)
+    }
+
+    pub fn dfs_events_from(&self, start: usize) -> DfsEvents {
+        search::dfs_events_from(self, start)
+    }
+}
+
+impl<T, E> std::fmt::Display for AdjGraph<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for v in self.vertexs.iter() {
+            write!(f, "{}: ", v.idx)?;
+            for e in self.out_edges(v.idx) {
+                write!(f, "{} ", e.other(v.idx))?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_edges_traverse() {
+        let g: AdjGraph<()> =
+            AdjGraph::build_undigraph(6, vec![(0, 1), (1, 2), (0, 3), (3, 2), (4, 5)]);
+        assert_eq!((g.V(), g.E()), (6, 5));
+        assert_eq!(g.get_vertex(2).degree(), 2);
+
+        let bfs = g.breadth_first_search(0);
+        let depth: Vec<u32> = bfs.iter().map(|v| v.depth()).collect();
+        assert_eq!(depth, vec![0, 1, 2, 1, u32::MAX, u32::MAX]);
+        assert_eq!(bfs[2].ancestor(), Some(1));
+        assert!(!bfs[4].is_visited());
+
+        let dfs = g.depth_first_search();
+        assert!(dfs.iter().all(|v| v.is_visited()));
+        assert_eq!(dfs[4].finish_time(), 12);
+        for v in 0..g.V() {
+            if let Some(u) = dfs[v].ancestor() {
+                assert!(dfs[u].discover_time() < dfs[v].discover_time());
+                assert!(dfs[v].finish_time() < dfs[u].finish_time());
+            }
+        }
+
+        let digraph: AdjGraph<()> = AdjGraph::build_digraph(3, vec![(0, 1), (1, 2)]);
+        assert_eq!(digraph.breadth_first_search(2)[0].depth(), u32::MAX);
+        assert_eq!(digraph.breadth_first_search(0)[2].depth(), 2);
+    }
+
+    #[test]
+    fn payloads_carry_domain_data() {
+        let mut g: AdjGraph<(&str, (f64, f64)), f64> = AdjGraph::new(false);
+        let a = g.add_vertex(("a", (0.0, 0.0)));
+        let b = g.add_vertex(("b", (3.0, 4.0)));
+        let c = g.add_vertex(("c", (3.0, 0.0)));
+        let dist = |g: &AdjGraph<(&str, (f64, f64)), f64>, u: usize, v: usize| {
+            let ((x1, y1), (x2, y2)) = (g.info(u).1, g.info(v).1);
+            ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
+        };
+        for &(u, v) in &[(a, b), (a, c), (c, b)] {
+            let d = dist(&g, u, v);
+            assert!(g.add_edge(u, v, d));
+        }
+        assert!(!g.add_edge(a, 7, 1.0));
+        assert!(!g.add_edge(a, b, f64::NAN));
+        assert!(!GraphMut::add_edge(&mut g, a, b, f64::NAN));
+        assert_eq!(g.E(), 3);
+
+        let (distance, ancestor) = search::dijkstra(&g, b);
+        assert_eq!(distance, vec![5.0, 0.0, 4.0]);
+        assert_eq!(g.info(ancestor[a].unwrap()).0, "b");
+
+        g.info_mut(c).0 = "corner";
+        *g.edge_mut(0).data_mut() = 10.0;
+        assert_eq!(search::dijkstra(&g, b).0[a], 7.0);
+        assert_eq!(g.get_vertex(c).info().0, "corner");
+        assert_eq!(format!("{}", g), "0: 1 2 \n1: 0 2 \n2: 0 1 \n\n");
+    }
+
+    #[test]
+    fn nan_payloads_are_rejected() {
+        let mut g: AdjGraph<(), f64> = AdjGraph::new_digraph(2);
+        assert!(!g.add_edge(0, 1, f64::NAN));
+        assert!(g.add_edge(0, 1, 1.5));
+        assert_eq!(g.E(), 1);
+    }
+}
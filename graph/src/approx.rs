@@ -0,0 +1,311 @@
+// Approximation algorithms of CLRS chapter 35 on undirected graphs: the
+// 2-approximate vertex cover and tours for the metric travelling salesman
+// problem.
+//
+// Tours are over the metric closure of the graph: the cost of going from u
+// to v is their shortest-path distance, which is the edge weight itself on
+// a complete graph that obeys the triangle inequality. A tour lists every
+// vertex once, starting at 0, and returns to 0 at the end; it needs a
+// connected graph with non-negative weights.
+use std::cmp::Ordering;
+
+use crate::matching;
+use crate::search;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+fn less<W: Weight>(a: W, b: W) -> bool {
+    a.cmp_weight(&b) == Ordering::Less
+}
+
+// Shortest-path distance between every pair; None if the graph is
+// disconnected or empty.
+fn metric_closure<W: Weight>(g: &UndiGraph<W>) -> Option<Vec<Vec<W>>> {
+    assert!(
+        g.iter_edges().all(|e| !less(e.weight(), W::zero())),
+        "edge weights must be non-negative"
+    );
+    if g.V() == 0 {
+        return None;
+    }
+    let d: Vec<Vec<W>> = (0..g.V()).map(|s| search::dijkstra(g, s).0).collect();
+    if d[0].iter().any(|w| w.is_infinite()) {
+        return None;
+    }
+    Some(d)
+}
+
+fn tour_cost<W: Weight>(d: &[Vec<W>], tour: &[usize]) -> W {
+    (0..tour.len()).fold(W::zero(), |acc, i| {
+        acc.saturating_plus(d[tour[i]][tour[(i + 1) % tour.len()]])
+    })
+}
+
+// The vertices of `walk` in order of their first visit.
+fn shortcut(n: usize, walk: &[usize]) -> Vec<usize> {
+    let mut seen = vec![false; n];
+    walk.iter()
+        .copied()
+        .filter(|&v| !std::mem::replace(&mut seen[v], true))
+        .collect()
+}
+
+// Preorder of a spanning tree given as edges, from vertex 0.
+fn preorder(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    let mut order = vec![];
+    let mut seen = vec![false; n];
+    let mut stack = vec![0];
+    while let Some(u) = stack.pop() {
+        if std::mem::replace(&mut seen[u], true) {
+            continue;
+        }
+        order.push(u);
+        stack.extend(adj[u].iter().rev().filter(|&&v| !seen[v]));
+    }
+    order
+}
+
+// Hierholzer's algorithm on a connected multigraph whose degrees are all
+// even, from vertex 0.
+fn euler_circuit(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut adj = vec![vec![]; n];
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        adj[u].push((v, i));
+        adj[v].push((u, i));
+    }
+    let mut used = vec![false; edges.len()];
+    let mut circuit = vec![];
+    let mut stack = vec![0];
+    while let Some(&u) = stack.last() {
+        match adj[u].pop() {
+            Some((v, i)) if !used[i] => {
+                used[i] = true;
+                stack.push(v);
+            }
+            Some(_) => {}
+            None => circuit.push(stack.pop().unwrap()),
+        }
+    }
+    circuit.reverse();
+    circuit
+}
+
+// Repeatedly reverses the stretch between two tour edges while that
+// shortens the tour.
+fn improve_two_opt<W: Weight>(d: &[Vec<W>], tour: &mut [usize]) {
+    let n = tour.len();
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..n.saturating_sub(2) {
+            for j in i + 2..n {
+                let (a, b) = (tour[i], tour[i + 1]);
+                let (c, e) = (tour[j], tour[(j + 1) % n]);
+                if e == a {
+                    continue;
+                }
+                let before = d[a][b].saturating_plus(d[c][e]);
+                let after = d[a][c].saturating_plus(d[b][e]);
+                if less(after, before) {
+                    tour[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+}
+
+// Approximation algorithms
+impl<W: Weight> UndiGraph<W> {
+    // APPROX-VERTEX-COVER: both ends of an uncovered edge, until every edge
+    // is covered; at most twice the size of a minimum cover.
+    pub fn approx_vertex_cover(&self) -> (usize, Vec<usize>) {
+        let mut in_cover = vec![false; self.V()];
+        for e in self.iter_edges() {
+            let (u, v) = e.ends();
+            if !in_cover[u] && !in_cover[v] {
+                in_cover[u] = true;
+                in_cover[v] = true;
+            }
+        }
+        let cover: Vec<usize> = (0..self.V()).filter(|&v| in_cover[v]).collect();
+        (cover.len(), cover)
+    }
+
+    // APPROX-TSP-TOUR: the preorder walk of a minimum spanning tree, at
+    // most twice the cost of an optimal tour. An MST of the graph is also
+    // one of its metric closure.
+    pub fn approx_tsp_tour(&self) -> Option<(W, Vec<usize>)> {
+        let d = metric_closure(self)?;
+        let tree: Vec<(usize, usize)> = self.prim_mst().iter().map(|e| e.ends()).collect();
+        let tour = preorder(self.V(), &tree);
+        Some((tour_cost(&d, &tour), tour))
+    }
+
+    // Christofides: a minimum spanning tree plus a minimum-weight perfect
+    // matching of its odd-degree vertices has an Euler circuit, and
+    // skipping repeated vertices on it gives a tour at most 3/2 of the
+    // optimum.
+    pub fn christofides_tour(&self) -> Option<(W, Vec<usize>)> {
+        let d = metric_closure(self)?;
+        let n = self.V();
+        let mut edges: Vec<(usize, usize)> = self.prim_mst().iter().map(|e| e.ends()).collect();
+        let mut degree = vec![0; n];
+        for &(u, v) in edges.iter() {
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+        let odd: Vec<usize> = (0..n).filter(|&v| degree[v] % 2 == 1).collect();
+        edges.extend(matching::min_weight_perfect_matching(&d, &odd));
+        let tour = shortcut(n, &euler_circuit(n, &edges));
+        Some((tour_cost(&d, &tour), tour))
+    }
+
+    // 2-opt local search from `tour`, which must list every vertex once;
+    // the result is never worse. Rotated to start at vertex 0.
+    pub fn two_opt(&self, tour: &[usize]) -> Option<(W, Vec<usize>)> {
+        let mut seen = vec![false; self.V()];
+        assert!(
+            tour.len() == self.V() && tour.iter().all(|&v| !std::mem::replace(&mut seen[v], true)),
+            "a tour lists every vertex once"
+        );
+        let d = metric_closure(self)?;
+        let mut tour = tour.to_vec();
+        improve_two_opt(&d, &mut tour);
+        let start = tour.iter().position(|&v| v == 0).unwrap();
+        tour.rotate_left(start);
+        Some((tour_cost(&d, &tour), tour))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lcg, random_edges};
+
+    // complete graph on random points of the plane
+    fn euclidean(n: usize, seed: u64) -> UndiGraph<f64> {
+        let mut next = lcg(seed);
+        let points: Vec<(f64, f64)> = (0..n)
+            .map(|_| ((next() % 100) as f64, (next() % 100) as f64))
+            .collect();
+        let mut edges = vec![];
+        for u in 0..n {
+            for v in u + 1..n {
+                let (dx, dy) = (points[u].0 - points[v].0, points[u].1 - points[v].1);
+                edges.push((u, v, (dx * dx + dy * dy).sqrt()));
+            }
+        }
+        UndiGraph::build_graph(n, edges)
+    }
+
+    // every tour from vertex 0, by permutations of the rest
+    fn optimal_tour(d: &[Vec<f64>]) -> f64 {
+        fn extend(d: &[Vec<f64>], tour: &mut Vec<usize>, best: &mut f64) {
+            if tour.len() == d.len() {
+                *best = best.min(tour_cost(d, tour));
+                return;
+            }
+            for v in 1..d.len() {
+                if !tour.contains(&v) {
+                    tour.push(v);
+                    extend(d, tour, best);
+                    tour.pop();
+                }
+            }
+        }
+        let mut best = f64::INFINITY;
+        extend(d, &mut vec![0], &mut best);
+        best
+    }
+
+    fn is_tour(n: usize, tour: &[usize]) -> bool {
+        let mut sorted = tour.to_vec();
+        sorted.sort();
+        tour[0] == 0 && sorted == (0..n).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn tours_stay_within_their_bounds() {
+        for seed in 0..10 {
+            let g = euclidean(8, seed);
+            let d = metric_closure(&g).unwrap();
+            let opt = optimal_tour(&d);
+
+            let (cost, tour) = g.approx_tsp_tour().unwrap();
+            assert!(is_tour(8, &tour));
+            assert!((cost - tour_cost(&d, &tour)).abs() < 1e-9);
+            assert!(cost <= 2.0 * opt + 1e-9);
+
+            let (better, improved) = g.two_opt(&tour).unwrap();
+            assert!(is_tour(8, &improved));
+            assert!(better <= cost + 1e-9 && better >= opt - 1e-9);
+
+            let (cost, tour) = g.christofides_tour().unwrap();
+            assert!(is_tour(8, &tour));
+            assert!(cost <= 1.5 * opt + 1e-9);
+        }
+
+        // a path graph: the hops back go through the metric closure
+        let g = UndiGraph::build_graph(4, vec![(0, 1, 1), (1, 2, 2), (2, 3, 3)]);
+        assert_eq!(g.approx_tsp_tour(), Some((12, vec![0, 1, 2, 3])));
+        let (cost, tour) = g.christofides_tour().unwrap();
+        assert!(cost == 12 && is_tour(4, &tour));
+        assert_eq!(g.two_opt(&[2, 0, 3, 1]), Some((12, vec![0, 1, 2, 3])));
+        assert_eq!(
+            UndiGraph::build_graph(3, vec![(0, 1, 1)]).approx_tsp_tour(),
+            None
+        );
+    }
+
+    #[test]
+    fn christofides_matches_many_odd_vertices_exactly() {
+        let g = euclidean(40, 7);
+        let d = metric_closure(&g).unwrap();
+        let all: Vec<usize> = (0..40).collect();
+        let pairs = matching::min_weight_perfect_matching(&d, &all);
+        let mut ends: Vec<usize> = pairs.iter().flat_map(|&(u, v)| vec![u, v]).collect();
+        ends.sort();
+        assert_eq!(ends, all);
+        // no two pairs are cheaper swapped, which closest-pair-first misses
+        for &(a, b) in &pairs {
+            for &(c, e) in pairs.iter().filter(|&&p| p != (a, b)) {
+                let cost = d[a][b] + d[c][e];
+                assert!(cost <= d[a][c] + d[b][e] + 1e-9);
+                assert!(cost <= d[a][e] + d[b][c] + 1e-9);
+            }
+        }
+        let (_, tour) = g.christofides_tour().unwrap();
+        assert!(is_tour(40, &tour));
+    }
+
+    #[test]
+    fn vertex_cover_within_twice_the_minimum() {
+        let mut next = lcg(3);
+        for _ in 0..20 {
+            let n = 8;
+            let edges = random_edges(n, 12, &mut next, |_| 1);
+            let g = UndiGraph::build_graph(n, edges.clone());
+            let (size, cover) = g.approx_vertex_cover();
+            assert_eq!(size, cover.len());
+            assert!(edges
+                .iter()
+                .all(|&(u, v, _)| cover.contains(&u) || cover.contains(&v)));
+            let minimum = (0..1u32 << n)
+                .filter(|mask| {
+                    edges
+                        .iter()
+                        .all(|&(u, v, _)| mask >> u & 1 == 1 || mask >> v & 1 == 1)
+                })
+                .map(|mask| mask.count_ones() as usize)
+                .min()
+                .unwrap();
+            assert!(size <= 2 * minimum);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// Weighted assignment by the Hungarian algorithm, O(n^2 m) for n rows and
+// m >= n columns, on a dense matrix (as in `matrix_graph`, an infinite entry
+// is a pair that may not be assigned) or on a weighted bipartite graph.
+//
+// An assignment pairs as many rows with columns as the allowed entries
+// permit, and among those picks the least total cost or the greatest
+// total weight.
+use std::cmp::Ordering;
+
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    MinCost,
+    MaxWeight,
+}
+
+// Shortest augmenting paths with row and column potentials u and v, so
+// that a[i][j] - u[i] - v[j] >= 0 and is zero on every assigned pair.
+// Needs rows <= columns; returns the row of every column, if any.
+fn solve(a: &[Vec<f64>], m: usize) -> Vec<Option<usize>> {
+    let n = a.len();
+    // 1-based: column 0 is the virtual one the current row starts from
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; m + 1];
+    let mut row_of = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_slack = vec![f64::INFINITY; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let (mut delta, mut j1) = (f64::INFINITY, 0);
+            for j in (1..=m).filter(|&j| !used[j]) {
+                let slack = a[i0 - 1][j - 1] - u[i0] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = j0;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+        // flip the path back to the virtual column
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+    (1..=m)
+        .map(|j| {
+            if row_of[j] == 0 {
+                None
+            } else {
+                Some(row_of[j] - 1)
+            }
+        })
+        .collect()
+}
+
+// The column assigned to every row, None where the row is left out, and
+// the total of the assigned entries. Rows may outnumber columns or the
+// other way round; all rows must have the same length.
+pub fn hungarian<W: Weight>(matrix: &[Vec<W>], objective: Objective) -> (W, Vec<Option<usize>>) {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, |r| r.len());
+    assert!(
+        matrix.iter().all(|r| r.len() == cols),
+        "rows must have the same length"
+    );
+    if rows == 0 || cols == 0 {
+        return (W::zero(), vec![None; rows]);
+    }
+
+    // a forbidden pair costs more than any assignment of allowed ones, so
+    // it is only taken when nothing else is left, and then dropped
+    let finite = matrix.iter().flatten().filter(|w| !w.is_infinite());
+    let forbidden = 1.0 + finite.map(|w| w.to_f64().abs()).sum::<f64>();
+    let cost = |w: W| match objective {
+        _ if w.is_infinite() => forbidden,
+        Objective::MinCost => w.to_f64(),
+        Objective::MaxWeight => -w.to_f64(),
+    };
+
+    let mut assigned = vec![None; rows];
+    if rows <= cols {
+        let a: Vec<Vec<f64>> = matrix
+            .iter()
+            .map(|r| r.iter().map(|&w| cost(w)).collect())
+            .collect();
+        for (j, i) in solve(&a, cols).into_iter().enumerate() {
+            if let Some(i) = i {
+                assigned[i] = Some(j);
+            }
+        }
+    } else {
+        let a: Vec<Vec<f64>> = (0..cols)
+            .map(|j| (0..rows).map(|i| cost(matrix[i][j])).collect())
+            .collect();
+        for (i, j) in solve(&a, rows).into_iter().enumerate() {
+            assigned[i] = j;
+        }
+    }
+
+    let mut total = W::zero();
+    for (i, j) in assigned.iter_mut().enumerate() {
+        match *j {
+            Some(c) if matrix[i][c].is_infinite() => *j = None,
+            Some(c) => total = total.saturating_plus(matrix[i][c]),
+            None => {}
+        }
+    }
+    (total, assigned)
+}
+
+// Weighted bipartite matching
+impl<W: Weight> UndiGraph<W> {
+    // The best assignment between `left` and the other vertices; every edge
+    // must join the two sides. Returns the total weight and the matched
+    // edges as (left, right, weight).
+    pub fn assignment(&self, left: &[usize], objective: Objective) -> (W, Vec<(usize, usize, W)>) {
+        let mut row = vec![None; self.V()];
+        for (i, &v) in left.iter().enumerate() {
+            row[v] = Some(i);
+        }
+        let right: Vec<usize> = (0..self.V()).filter(|&v| row[v].is_none()).collect();
+        let mut column = vec![None; self.V()];
+        for (j, &v) in right.iter().enumerate() {
+            column[v] = Some(j);
+        }
+
+        // parallel edges: keep the one the objective prefers
+        let mut matrix = vec![vec![W::infinity(); right.len()]; left.len()];
+        for e in self.iter_edges() {
+            let (a, b) = e.ends();
+            let (i, j) = match (row[a], row[b]) {
+                (Some(i), None) => (i, column[b].unwrap()),
+                (None, Some(i)) => (i, column[a].unwrap()),
+                _ => panic!("edge {} joins two vertices on the same side", e),
+            };
+            let better = match objective {
+                Objective::MinCost => Ordering::Less,
+                Objective::MaxWeight => Ordering::Greater,
+            };
+            let entry = &mut matrix[i][j];
+            if entry.is_infinite() || e.weight().cmp_weight(entry) == better {
+                *entry = e.weight();
+            }
+        }
+
+        let (total, assigned) = hungarian(&matrix, objective);
+        let pairs = assigned
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, j)| j.map(|j| (left[i], right[j], matrix[i][j])))
+            .collect();
+        (total, pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    // every injective assignment of the smaller side
+    fn brute_force(matrix: &[Vec<f32>], objective: Objective) -> f32 {
+        fn extend(m: &[Vec<f32>], i: usize, used: &mut Vec<bool>, sum: f32, res: &mut Vec<f32>) {
+            if i == m.len() {
+                res.push(sum);
+                return;
+            }
+            for j in 0..used.len() {
+                if !used[j] {
+                    used[j] = true;
+                    extend(m, i + 1, used, sum + m[i][j], res);
+                    used[j] = false;
+                }
+            }
+        }
+        let (rows, cols) = (matrix.len(), matrix[0].len());
+        let m: Vec<Vec<f32>> = if rows <= cols {
+            matrix.to_vec()
+        } else {
+            (0..cols)
+                .map(|j| (0..rows).map(|i| matrix[i][j]).collect())
+                .collect()
+        };
+        let mut sums = vec![];
+        extend(&m, 0, &mut vec![false; m[0].len()], 0.0, &mut sums);
+        match objective {
+            Objective::MinCost => sums.iter().cloned().fold(f32::INFINITY, f32::min),
+            Objective::MaxWeight => sums.iter().cloned().fold(f32::NEG_INFINITY, f32::max),
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_on_rectangular_matrices() {
+        let mut next = lcg(11);
+        for &(rows, cols) in &[(4, 4), (3, 6), (6, 3), (5, 5), (1, 4)] {
+            for _ in 0..10 {
+                let matrix: Vec<Vec<f32>> = (0..rows)
+                    .map(|_| (0..cols).map(|_| (next() % 50) as f32 - 10.0).collect())
+                    .collect();
+                for &objective in &[Objective::MinCost, Objective::MaxWeight] {
+                    let (total, assigned) = hungarian(&matrix, objective);
+                    assert_eq!(total, brute_force(&matrix, objective));
+                    assert_eq!(assigned.iter().flatten().count(), rows.min(cols));
+                    let mut cols_used: Vec<usize> = assigned.iter().flatten().cloned().collect();
+                    cols_used.sort();
+                    cols_used.dedup();
+                    assert_eq!(cols_used.len(), rows.min(cols));
+                    let sum: f32 = (0..rows)
+                        .filter_map(|i| assigned[i].map(|j| matrix[i][j]))
+                        .sum();
+                    assert_eq!(sum, total);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn forbidden_pairs_and_bipartite_graphs() {
+        let inf = f32::INFINITY;
+        // row 0 can only take column 0, which row 1 would rather have
+        let matrix = vec![
+            vec![5.0, inf, inf],
+            vec![1.0, 9.0, inf],
+            vec![inf, inf, inf],
+        ];
+        assert_eq!(
+            hungarian(&matrix, Objective::MinCost),
+            (14.0, vec![Some(0), Some(1), None])
+        );
+
+        // workers 0..3, shifts 3..6
+        let edges = vec![
+            (0, 3, 4),
+            (0, 4, 1),
+            (1, 3, 2),
+            (1, 5, 7),
+            (2, 4, 3),
+            (2, 5, 6),
+            (0, 4, 8),
+        ];
+        let g = UndiGraph::build_graph(6, edges);
+        let (cost, pairs) = g.assignment(&[0, 1, 2], Objective::MinCost);
+        assert_eq!(cost, 9);
+        assert_eq!(pairs, vec![(0, 4, 1), (1, 3, 2), (2, 5, 6)]);
+        let (weight, pairs) = g.assignment(&[0, 1, 2], Objective::MaxWeight);
+        assert_eq!(weight, 16);
+        assert_eq!(pairs, vec![(0, 4, 8), (1, 3, 2), (2, 5, 6)]);
+        assert_eq!(hungarian::<i32>(&[], Objective::MinCost), (0, vec![]));
+    }
+}
@@ -0,0 +1,151 @@
+// Command line parsing; no dependencies, so by hand.
+
+pub const USAGE: &str = "\
+usage: clrs-graph <command> [options]
+
+commands:
+  sp      single-source shortest paths (--algo dijkstra|bellman-ford|dag)
+  apsp    all-pairs shortest paths (--algo floyd-warshall|squaring)
+  scc     strongly connected components
+  topo    topological order
+  mst     minimum spanning forest (--algo kruskal|prim)
+  flow    maximum flow and minimum cut from --source to --target
+
+options:
+  -i, --input FILE       read the graph from FILE instead of stdin
+  -f, --format FORMAT    edges: V, then E, then `from to [weight]` lines
+                         labeled: `from to [weight]` lines with named vertices
+  -o, --output OUTPUT    text, json or dot
+  -s, --source VERTEX    vertex id, or label with --format labeled
+  -t, --target VERTEX
+  -a, --algo ALGO
+  -u, --undirected       read edges as undirected (mst always does)
+  -h, --help
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    ShortestPaths,
+    AllPairs,
+    Components,
+    Topo,
+    SpanningTree,
+    Flow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Edges,
+    Labeled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    Text,
+    Json,
+    Dot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub algo: String,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub input: Option<String>,
+    pub format: Format,
+    pub output: Output,
+    pub undirected: bool,
+}
+
+// The algorithms a command accepts, default first.
+fn algos(command: Command) -> &'static [&'static str] {
+    match command {
+        Command::ShortestPaths => &["dijkstra", "bellman-ford", "dag"],
+        Command::AllPairs => &["floyd-warshall", "squaring"],
+        Command::SpanningTree => &["kruskal", "prim"],
+        Command::Components | Command::Topo | Command::Flow => &[],
+    }
+}
+
+// None for `-h`/`--help`.
+pub fn parse_args(argv: &[String]) -> Result<Option<Args>, String> {
+    if argv.iter().any(|a| a == "-h" || a == "--help") {
+        return Ok(None);
+    }
+
+    let mut argv = argv.iter();
+    let command = match argv.next().map(String::as_str) {
+        Some("sp") => Command::ShortestPaths,
+        Some("apsp") => Command::AllPairs,
+        Some("scc") => Command::Components,
+        Some("topo") => Command::Topo,
+        Some("mst") => Command::SpanningTree,
+        Some("flow") => Command::Flow,
+        Some(other) => return Err(format!("unknown command `{}`", other)),
+        None => return Err("missing command".to_string()),
+    };
+
+    let mut args = Args {
+        command,
+        algo: algos(command).first().unwrap_or(&"").to_string(),
+        source: None,
+        target: None,
+        input: None,
+        format: Format::Edges,
+        output: Output::Text,
+        undirected: command == Command::SpanningTree,
+    };
+
+    while let Some(arg) = argv.next() {
+        // `--name=value` or `--name value`
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if name == "-u" || name == "--undirected" {
+            args.undirected = true;
+            continue;
+        }
+        let value = match inline.or_else(|| argv.next().cloned()) {
+            Some(value) => value,
+            None => return Err(format!("`{}` needs a value", name)),
+        };
+        match name {
+            "-a" | "--algo" => {
+                if !algos(command).contains(&value.as_str()) {
+                    return Err(format!("unknown algorithm `{}` for this command", value));
+                }
+                args.algo = value;
+            }
+            "-s" | "--source" => args.source = Some(value),
+            "-t" | "--target" => args.target = Some(value),
+            "-i" | "--input" => args.input = Some(value),
+            "-f" | "--format" => {
+                args.format = match value.as_str() {
+                    "edges" => Format::Edges,
+                    "labeled" => Format::Labeled,
+                    _ => return Err(format!("unknown input format `{}`", value)),
+                }
+            }
+            "-o" | "--output" => {
+                args.output = match value.as_str() {
+                    "text" => Output::Text,
+                    "json" => Output::Json,
+                    "dot" => Output::Dot,
+                    _ => return Err(format!("unknown output `{}`", value)),
+                }
+            }
+            _ => return Err(format!("unknown option `{}`", name)),
+        }
+    }
+
+    let needs_source = matches!(command, Command::ShortestPaths | Command::Flow);
+    if needs_source && args.source.is_none() {
+        return Err("missing --source".to_string());
+    }
+    if command == Command::Flow && args.target.is_none() {
+        return Err("missing --target".to_string());
+    }
+    Ok(Some(args))
+}
@@ -0,0 +1,98 @@
+use std::io::BufRead;
+
+use graph::directed_graph::DictetedGraph;
+use graph::labeled::parse_labeled_graph;
+use graph::traits::Graph;
+
+use crate::args::Format;
+
+// The graph as read: vertex names (the ids themselves for `--format
+// edges`) and the edge list. Each command builds the representation it
+// needs from it.
+pub struct Input {
+    pub names: Vec<String>,
+    pub edges: Vec<(usize, usize, f64)>,
+}
+
+impl Input {
+    pub fn vertex_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, v: usize) -> &str {
+        &self.names[v]
+    }
+
+    pub fn vertex(&self, name: &str) -> Result<usize, String> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .ok_or_else(|| format!("unknown vertex `{}`", name))
+    }
+}
+
+pub fn read_input<R: BufRead>(reader: R, format: Format) -> Result<Input, String> {
+    match format {
+        Format::Edges => read_edges(reader),
+        Format::Labeled => {
+            let g = parse_labeled_graph(DictetedGraph::<f64>::new(0), reader)
+                .map_err(|e| e.to_string())?;
+            Ok(Input {
+                names: g.labels().labels().to_vec(),
+                edges: g.graph().edges(),
+            })
+        }
+    }
+}
+
+// The testcases layout: the vertex count, the edge count (optional), then
+// one `from to [weight]` edge per line; the weight defaults to one.
+fn read_edges<R: BufRead>(reader: R) -> Result<Input, String> {
+    let mut v = None;
+    let mut counted = false;
+    let mut edges = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        let err = |msg: String| format!("line {}: {}", i + 1, msg);
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        if fields.is_empty() || fields[0].starts_with('#') {
+            continue;
+        }
+        let parse_vertex = |field: &str| match field.parse::<usize>() {
+            Ok(id) if Some(id) < v => Ok(id),
+            _ => Err(err(format!("invalid vertex `{}`", field))),
+        };
+        match (v, fields.as_slice()) {
+            (None, [count]) => {
+                v = Some(
+                    count
+                        .parse()
+                        .map_err(|_| err(format!("invalid vertex count `{}`", count)))?,
+                )
+            }
+            (None, _) => return Err(err("expected the vertex count".to_string())),
+            (Some(_), [count]) if !counted && edges.is_empty() => {
+                count
+                    .parse::<usize>()
+                    .map_err(|_| err(format!("invalid edge count `{}`", count)))?;
+                counted = true;
+            }
+            (Some(_), [from, to]) => edges.push((parse_vertex(from)?, parse_vertex(to)?, 1.0)),
+            (Some(_), [from, to, weight]) => {
+                let weight = weight
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|w| !w.is_nan())
+                    .ok_or_else(|| err(format!("invalid weight `{}`", weight)))?;
+                edges.push((parse_vertex(from)?, parse_vertex(to)?, weight));
+            }
+            _ => return Err(err(format!("expected an edge, got `{}`", line.trim()))),
+        }
+    }
+
+    let v = v.ok_or_else(|| "empty input".to_string())?;
+    Ok(Input {
+        names: (0..v).map(|i| i.to_string()).collect(),
+        edges,
+    })
+}
@@ -0,0 +1,344 @@
+// clrs-graph: run one algorithm on a graph read from a file or stdin and
+// print the result as text, JSON or DOT. See `clrs-graph --help`.
+mod args;
+mod input;
+mod output;
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+use graph::directed_graph::DictetedGraph;
+use graph::flow;
+use graph::matrix_graph::{par_faster_all_shortest_paths, par_floyd_warshall};
+use graph::search;
+use graph::traits::Graph;
+use graph::undirected_graph::UndiGraph;
+
+use args::{parse_args, Args, Command, USAGE};
+use input::{read_input, Input};
+
+// What a command found, in vertex ids; `output` turns it into text.
+pub enum Report {
+    Paths {
+        source: usize,
+        target: Option<usize>,
+        distance: Vec<f64>,
+        ancestor: Vec<Option<usize>>,
+    },
+    AllPairs(Vec<Vec<f64>>),
+    Components(Vec<Vec<usize>>),
+    Order(Vec<usize>),
+    Forest {
+        edges: Vec<(usize, usize, f64)>,
+        total: f64,
+    },
+    Flow {
+        source: usize,
+        target: usize,
+        value: f64,
+        // (from, to, flow, capacity) for every edge, from and to in the
+        // direction of the flow
+        flows: Vec<(usize, usize, f64, f64)>,
+        cut: Vec<(usize, usize, f64)>,
+    },
+}
+
+fn directed_only(args: &Args, what: &str) -> Result<(), String> {
+    if args.undirected {
+        return Err(format!("{} needs a directed graph", what));
+    }
+    Ok(())
+}
+
+fn shortest_paths(args: &Args, input: &Input) -> Result<Report, String> {
+    let source = input.vertex(args.source.as_deref().unwrap())?;
+    let target = args
+        .target
+        .as_deref()
+        .map(|t| input.vertex(t))
+        .transpose()?;
+    let n = input.vertex_count();
+    let negative = input.edges.iter().any(|e| e.2 < 0.0);
+
+    let (distance, ancestor) = match args.algo.as_str() {
+        "dijkstra" if negative => {
+            return Err("dijkstra needs non-negative weights, try --algo bellman-ford".to_string())
+        }
+        "dijkstra" if args.undirected => {
+            search::dijkstra(&UndiGraph::build_graph(n, input.edges.clone()), source)
+        }
+        "dijkstra" => search::dijkstra(&DictetedGraph::build_graph(n, input.edges.clone()), source),
+        "bellman-ford" => {
+            let res = if args.undirected {
+                search::bellman_ford(&UndiGraph::build_graph(n, input.edges.clone()), source)
+            } else {
+                search::bellman_ford(&DictetedGraph::build_graph(n, input.edges.clone()), source)
+            };
+            res.map_err(|_| "negative cycle reachable from the source".to_string())?
+        }
+        _ => {
+            directed_only(args, "--algo dag")?;
+            let mut g = DictetedGraph::build_graph(n, input.edges.clone());
+            if g.topological_order().is_none() {
+                return Err("--algo dag needs an acyclic graph".to_string());
+            }
+            g.shortest_path_find_by_sort(source);
+            let distance = g.iter().map(|v| v.borrow().get_distance()).collect();
+            let ancestor = (0..n)
+                .map(|v| g.get_ancestor(v).map(|a| a.borrow().idx()))
+                .collect();
+            (distance, ancestor)
+        }
+    };
+
+    Ok(Report::Paths {
+        source,
+        target,
+        distance,
+        ancestor,
+    })
+}
+
+fn all_pairs(args: &Args, input: &Input) -> Result<Report, String> {
+    let n = input.vertex_count();
+    let mut w = vec![vec![f64::INFINITY; n]; n];
+    for (i, row) in w.iter_mut().enumerate() {
+        row[i] = 0.0;
+    }
+    for &(from, to, weight) in input.edges.iter() {
+        if from != to {
+            w[from][to] = w[from][to].min(weight);
+            if args.undirected {
+                w[to][from] = w[to][from].min(weight);
+            }
+        } else if weight < 0.0 {
+            return Err("negative cycle".to_string());
+        }
+    }
+
+    let distance = match args.algo.as_str() {
+        "floyd-warshall" => par_floyd_warshall(&w),
+        _ => par_faster_all_shortest_paths(&w),
+    };
+    if (0..n).any(|i| distance[i][i] < 0.0) {
+        return Err("negative cycle".to_string());
+    }
+    Ok(Report::AllPairs(distance))
+}
+
+fn topo(args: &Args, input: &Input) -> Result<Report, String> {
+    directed_only(args, "topo")?;
+    let g = DictetedGraph::build_graph(input.vertex_count(), input.edges.clone());
+    match g.topological_order() {
+        Some(order) => Ok(Report::Order(order)),
+        None => {
+            let cycle: Vec<&str> = g
+                .find_cycle()
+                .unwrap()
+                .into_iter()
+                .map(|v| input.name(v))
+                .collect();
+            Err(format!("graph has a cycle: {}", cycle.join(" -> ")))
+        }
+    }
+}
+
+fn spanning_forest(args: &Args, input: &Input) -> Report {
+    let g = UndiGraph::build_graph(input.vertex_count(), input.edges.clone());
+    let forest = match args.algo.as_str() {
+        "kruskal" => g.kerskal_msf(),
+        _ => g.prim_msf(),
+    };
+    Report::Forest {
+        edges: forest
+            .edges()
+            .map(|e| {
+                let (v1, v2) = e.ends();
+                (v1, v2, e.weight())
+            })
+            .collect(),
+        total: forest.total_weight(),
+    }
+}
+
+type FlowResult = (f64, Vec<(usize, usize, f64, f64)>, Vec<(usize, usize, f64)>);
+
+fn solve_flow<G: Graph<Weight = f64>>(g: &G, source: usize, target: usize) -> FlowResult {
+    let flow = flow::edmonds_karp(g, source, target);
+    let flows = flow
+        .flows()
+        .iter()
+        .zip(g.edges())
+        .map(|(&(from, to, f), (_, _, cap))| (from, to, f, cap))
+        .collect();
+    (flow.value(), flows, flow.min_cut(g))
+}
+
+fn max_flow(args: &Args, input: &Input) -> Result<Report, String> {
+    let source = input.vertex(args.source.as_deref().unwrap())?;
+    let target = input.vertex(args.target.as_deref().unwrap())?;
+    if input.edges.iter().any(|e| e.2 < 0.0) {
+        return Err("capacities must be non-negative".to_string());
+    }
+    let n = input.vertex_count();
+    let (value, flows, cut) = if args.undirected {
+        solve_flow(
+            &UndiGraph::build_graph(n, input.edges.clone()),
+            source,
+            target,
+        )
+    } else {
+        solve_flow(
+            &DictetedGraph::build_graph(n, input.edges.clone()),
+            source,
+            target,
+        )
+    };
+    Ok(Report::Flow {
+        source,
+        target,
+        value,
+        flows,
+        cut,
+    })
+}
+
+fn run_on<R: BufRead, W: Write>(args: &Args, reader: R, out: &mut W) -> Result<(), String> {
+    let input = read_input(reader, args.format)?;
+    let report = match args.command {
+        Command::ShortestPaths => shortest_paths(args, &input)?,
+        Command::AllPairs => all_pairs(args, &input)?,
+        Command::Components => {
+            directed_only(args, "scc")?;
+            let g = DictetedGraph::build_graph(input.vertex_count(), input.edges.clone());
+            Report::Components(g.tarjan_scc())
+        }
+        Command::Topo => topo(args, &input)?,
+        Command::SpanningTree => spanning_forest(args, &input),
+        Command::Flow => max_flow(args, &input)?,
+    };
+    output::write_report(out, args, &input, &report).map_err(|e| e.to_string())
+}
+
+fn run(argv: &[String]) -> Result<(), String> {
+    let args = match parse_args(argv)? {
+        Some(args) => args,
+        None => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+    };
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match &args.input {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+            run_on(&args, BufReader::new(file), &mut out)
+        }
+        None => run_on(&args, io::stdin().lock(), &mut out),
+    }
+}
+
+fn main() {
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&argv) {
+        eprintln!("clrs-graph: {}", e);
+        if parse_args(&argv).is_err() {
+            eprint!("\n{}", USAGE);
+            std::process::exit(2);
+        }
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // CLRS Figure 24.6
+    const DIJKSTRA: &str =
+        "5\n10\n0 1 10\n0 3 5\n1 2 1\n1 3 2\n2 4 4\n3 1 3\n3 2 9\n3 4 2\n4 0 7\n4 2 6\n";
+
+    fn run_str(argv: &str, input: &str) -> Result<String, String> {
+        let argv: Vec<String> = argv.split_whitespace().map(String::from).collect();
+        let args = parse_args(&argv)?.unwrap();
+        let mut out = Vec::new();
+        run_on(&args, input.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn shortest_paths_in_every_output() {
+        assert_eq!(
+            run_str("sp -s 0 -t 2", DIJKSTRA).unwrap(),
+            "distance 9\npath 0 3 1 2\n"
+        );
+        for algo in &["dijkstra", "bellman-ford", "dag"] {
+            let res = run_str(&format!("sp --algo {} -s 0 -o json", algo), DIJKSTRA);
+            if *algo == "dag" {
+                assert_eq!(res, Err("--algo dag needs an acyclic graph".to_string()));
+            } else {
+                assert!(res
+                    .unwrap()
+                    .contains("{\"vertex\":\"4\",\"distance\":7,\"parent\":\"3\"}"));
+            }
+        }
+        let dot = run_str("sp -s 0 -t 4 -o dot", DIJKSTRA).unwrap();
+        assert!(dot.starts_with("digraph G {\n  0 [label=\"0\\nd=0\"];\n"));
+        assert!(dot.contains("  3 -> 4 [label=\"2\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("  3 -> 1 [label=\"3\"];\n"));
+
+        let negative = "3\n0 1 1\n1 2 -3\n2 1 1\n";
+        assert!(run_str("sp -s 0", negative)
+            .unwrap_err()
+            .contains("bellman-ford"));
+        assert_eq!(
+            run_str("sp -s 0 -a bellman-ford", negative),
+            Err("negative cycle reachable from the source".to_string())
+        );
+    }
+
+    #[test]
+    fn labeled_input_and_other_commands() {
+        let deps = "app serde 2\napp log 1\nserde syn 3\nlog syn 5\n";
+        assert_eq!(
+            run_str("topo -f labeled -o json", deps).unwrap(),
+            "{\"order\":[\"app\",\"log\",\"serde\",\"syn\"]}\n"
+        );
+        assert_eq!(
+            run_str("sp -f labeled -s app -t syn", deps).unwrap(),
+            "distance 5\npath app serde syn\n"
+        );
+        assert_eq!(
+            run_str("mst -f labeled -a prim", deps).unwrap(),
+            "app log 1\napp serde 2\nserde syn 3\ntotal 6\n"
+        );
+        assert_eq!(
+            run_str("flow -f labeled -s app -t syn", deps).unwrap(),
+            "max flow 3\nflow\napp serde 2\napp log 1\nserde syn 2\nlog syn 1\nmin cut\napp serde 2\napp log 1\n"
+        );
+        assert_eq!(
+            run_str("apsp -f labeled -u", "a b 1\nb c 2\n").unwrap(),
+            "a b c\na 0 1 3\nb 1 0 2\nc 3 2 0\n"
+        );
+        assert_eq!(
+            run_str("topo -f labeled", "a b 1\nb b 1\n"),
+            Err("line 2: edge `b b 1` rejected".to_string())
+        );
+        assert_eq!(run_str("scc", "3\n0 1\n1 0\n1 2\n").unwrap(), "2\n1 0\n");
+        assert_eq!(
+            run_str("topo", "3\n0 1\n1 0\n"),
+            Err("graph has a cycle: 0 -> 1".to_string())
+        );
+        assert_eq!(
+            run_str("sp -f labeled -s nope", deps),
+            Err("unknown vertex `nope`".to_string())
+        );
+        assert_eq!(
+            run_str("scc", "3\n2\n0 5\n"),
+            Err("line 3: invalid vertex `5`".to_string())
+        );
+        assert!(run_str("mst -a dijkstra", "").is_err());
+        assert!(run_str("flow -s 0", "").is_err());
+    }
+}
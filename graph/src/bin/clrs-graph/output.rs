@@ -0,0 +1,378 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use graph::search::path_to;
+
+use crate::args::{Args, Output};
+use crate::input::Input;
+use crate::Report;
+
+pub fn write_report<W: Write>(
+    out: &mut W,
+    args: &Args,
+    input: &Input,
+    report: &Report,
+) -> io::Result<()> {
+    match args.output {
+        Output::Text => write_text(out, input, report),
+        Output::Json => writeln!(out, "{}", to_json(input, report)),
+        Output::Dot => write_dot(out, args.undirected, input, report),
+    }
+}
+
+// Source first.
+fn path(ancestor: &[Option<usize>], target: usize) -> Vec<usize> {
+    let mut path = path_to(ancestor, target);
+    path.reverse();
+    path
+}
+
+fn number(w: f64) -> String {
+    if w.is_finite() {
+        w.to_string()
+    } else {
+        "inf".to_string()
+    }
+}
+
+fn names<'a>(input: &'a Input, ids: &[usize]) -> Vec<&'a str> {
+    ids.iter().map(|&v| input.name(v)).collect()
+}
+
+fn write_text<W: Write>(out: &mut W, input: &Input, report: &Report) -> io::Result<()> {
+    match report {
+        Report::Paths {
+            source,
+            target: Some(target),
+            distance,
+            ancestor,
+        } => {
+            if distance[*target].is_infinite() {
+                writeln!(
+                    out,
+                    "{} is unreachable from {}",
+                    input.name(*target),
+                    input.name(*source)
+                )
+            } else {
+                writeln!(out, "distance {}", number(distance[*target]))?;
+                writeln!(
+                    out,
+                    "path {}",
+                    names(input, &path(ancestor, *target)).join(" ")
+                )
+            }
+        }
+        // one `vertex distance parent` line per vertex
+        Report::Paths {
+            distance, ancestor, ..
+        } => {
+            for v in 0..input.vertex_count() {
+                let parent = ancestor[v].map_or("-", |a| input.name(a));
+                writeln!(out, "{} {} {}", input.name(v), number(distance[v]), parent)?;
+            }
+            Ok(())
+        }
+        Report::AllPairs(distance) => {
+            writeln!(out, "{}", input.names.join(" "))?;
+            for (v, row) in distance.iter().enumerate() {
+                let row: Vec<String> = row.iter().map(|&d| number(d)).collect();
+                writeln!(out, "{} {}", input.name(v), row.join(" "))?;
+            }
+            Ok(())
+        }
+        Report::Components(groups) => {
+            for group in groups {
+                writeln!(out, "{}", names(input, group).join(" "))?;
+            }
+            Ok(())
+        }
+        Report::Order(order) => writeln!(out, "{}", names(input, order).join(" ")),
+        Report::Forest { edges, total } => {
+            for &(v1, v2, w) in edges {
+                writeln!(out, "{} {} {}", input.name(v1), input.name(v2), number(w))?;
+            }
+            writeln!(out, "total {}", number(*total))
+        }
+        Report::Flow {
+            value, flows, cut, ..
+        } => {
+            writeln!(out, "max flow {}", number(*value))?;
+            writeln!(out, "flow")?;
+            for &(from, to, f, _) in flows.iter().filter(|e| e.2 > 0.0) {
+                writeln!(out, "{} {} {}", input.name(from), input.name(to), number(f))?;
+            }
+            writeln!(out, "min cut")?;
+            for &(from, to, cap) in cut {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    input.name(from),
+                    input.name(to),
+                    number(cap)
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Unreachable is null.
+fn json_number(w: f64) -> String {
+    if w.is_finite() {
+        w.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_list<T, F: Fn(&T) -> String>(items: &[T], f: F) -> String {
+    let items: Vec<String> = items.iter().map(f).collect();
+    format!("[{}]", items.join(","))
+}
+
+fn json_names(input: &Input, ids: &[usize]) -> String {
+    json_list(ids, |&v| json_str(input.name(v)))
+}
+
+fn json_edge(input: &Input, from: usize, to: usize, fields: &[(&str, f64)]) -> String {
+    let mut out = format!(
+        "{{\"from\":{},\"to\":{}",
+        json_str(input.name(from)),
+        json_str(input.name(to))
+    );
+    for (key, w) in fields {
+        out.push_str(&format!(",{}:{}", json_str(key), json_number(*w)));
+    }
+    out.push('}');
+    out
+}
+
+fn to_json(input: &Input, report: &Report) -> String {
+    match report {
+        Report::Paths {
+            source,
+            target: Some(target),
+            distance,
+            ancestor,
+        } => {
+            let path = if distance[*target].is_infinite() {
+                "null".to_string()
+            } else {
+                json_names(input, &path(ancestor, *target))
+            };
+            format!(
+                "{{\"source\":{},\"target\":{},\"distance\":{},\"path\":{}}}",
+                json_str(input.name(*source)),
+                json_str(input.name(*target)),
+                json_number(distance[*target]),
+                path
+            )
+        }
+        Report::Paths {
+            source,
+            distance,
+            ancestor,
+            ..
+        } => {
+            let vertices: Vec<usize> = (0..input.vertex_count()).collect();
+            let vertices = json_list(&vertices, |&v| {
+                let parent = ancestor[v].map_or("null".to_string(), |a| json_str(input.name(a)));
+                format!(
+                    "{{\"vertex\":{},\"distance\":{},\"parent\":{}}}",
+                    json_str(input.name(v)),
+                    json_number(distance[v]),
+                    parent
+                )
+            });
+            format!(
+                "{{\"source\":{},\"vertices\":{}}}",
+                json_str(input.name(*source)),
+                vertices
+            )
+        }
+        Report::AllPairs(distance) => format!(
+            "{{\"vertices\":{},\"distance\":{}}}",
+            json_list(&input.names, |n| json_str(n)),
+            json_list(distance, |row| json_list(row, |&d| json_number(d)))
+        ),
+        Report::Components(groups) => format!(
+            "{{\"components\":{}}}",
+            json_list(groups, |g| json_names(input, g))
+        ),
+        Report::Order(order) => format!("{{\"order\":{}}}", json_names(input, order)),
+        Report::Forest { edges, total } => format!(
+            "{{\"edges\":{},\"total\":{}}}",
+            json_list(edges, |&(v1, v2, w)| json_edge(
+                input,
+                v1,
+                v2,
+                &[("weight", w)]
+            )),
+            json_number(*total)
+        ),
+        Report::Flow {
+            source,
+            target,
+            value,
+            flows,
+            cut,
+        } => format!(
+            "{{\"source\":{},\"target\":{},\"value\":{},\"flows\":{},\"cut\":{}}}",
+            json_str(input.name(*source)),
+            json_str(input.name(*target)),
+            json_number(*value),
+            json_list(flows, |&(from, to, f, cap)| {
+                json_edge(input, from, to, &[("flow", f), ("capacity", cap)])
+            }),
+            json_list(cut, |&(from, to, cap)| {
+                json_edge(input, from, to, &[("capacity", cap)])
+            })
+        ),
+    }
+}
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+// DOT quoted string; a newline becomes DOT's `\n` line break.
+fn dot_str(s: &str) -> String {
+    let lines: Vec<String> = s
+        .split('\n')
+        .map(|l| l.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect();
+    format!("\"{}\"", lines.join("\\n"))
+}
+
+fn write_dot<W: Write>(
+    out: &mut W,
+    undirected: bool,
+    input: &Input,
+    report: &Report,
+) -> io::Result<()> {
+    let (kind, arrow) = if undirected {
+        ("graph", "--")
+    } else {
+        ("digraph", "->")
+    };
+    // normalizes an undirected edge for lookups
+    let key = |from: usize, to: usize| {
+        if undirected {
+            (from.min(to), from.max(to))
+        } else {
+            (from, to)
+        }
+    };
+    let edge_line =
+        |from: usize, to: usize, attrs: &str| format!("  {} {} {} [{}];", from, arrow, to, attrs);
+    let plain_edges = |out: &mut W, marked: &HashSet<(usize, usize)>| -> io::Result<()> {
+        for &(from, to, w) in input.edges.iter() {
+            let mut attrs = format!("label={}", dot_str(&number(w)));
+            if marked.contains(&key(from, to)) {
+                attrs = format!("{}, {}", attrs, HIGHLIGHT);
+            }
+            writeln!(out, "{}", edge_line(from, to, &attrs))?;
+        }
+        Ok(())
+    };
+    let node = |out: &mut W, v: usize, label: &str| -> io::Result<()> {
+        writeln!(out, "  {} [label={}];", v, dot_str(label))
+    };
+
+    writeln!(out, "{} G {{", kind)?;
+    match report {
+        // the shortest-path tree, or just the path to the target
+        Report::Paths {
+            target,
+            distance,
+            ancestor,
+            ..
+        } => {
+            for (v, &d) in distance.iter().enumerate() {
+                node(out, v, &format!("{}\nd={}", input.name(v), number(d)))?;
+            }
+            let marked: HashSet<(usize, usize)> = match target {
+                Some(t) if !distance[*t].is_infinite() => path(ancestor, *t)
+                    .windows(2)
+                    .map(|p| key(p[0], p[1]))
+                    .collect(),
+                Some(_) => HashSet::new(),
+                None => (0..input.vertex_count())
+                    .filter_map(|v| ancestor[v].map(|a| key(a, v)))
+                    .collect(),
+            };
+            plain_edges(out, &marked)?;
+        }
+        // the distance graph
+        Report::AllPairs(distance) => {
+            for v in 0..input.vertex_count() {
+                node(out, v, input.name(v))?;
+            }
+            for (from, row) in distance.iter().enumerate() {
+                for (to, &d) in row.iter().enumerate() {
+                    if from != to && d.is_finite() && (!undirected || from < to) {
+                        let attrs = format!("label={}", dot_str(&number(d)));
+                        writeln!(out, "{}", edge_line(from, to, &attrs))?;
+                    }
+                }
+            }
+        }
+        Report::Components(groups) => {
+            for (i, group) in groups.iter().enumerate() {
+                writeln!(out, "  subgraph cluster_{} {{", i)?;
+                for &v in group {
+                    write!(out, "  ")?;
+                    node(out, v, input.name(v))?;
+                }
+                writeln!(out, "  }}")?;
+            }
+            plain_edges(out, &HashSet::new())?;
+        }
+        Report::Order(order) => {
+            writeln!(out, "  rankdir=LR;")?;
+            for (i, &v) in order.iter().enumerate() {
+                node(out, v, &format!("{} ({})", input.name(v), i))?;
+            }
+            plain_edges(out, &HashSet::new())?;
+        }
+        Report::Forest { edges, .. } => {
+            for v in 0..input.vertex_count() {
+                node(out, v, input.name(v))?;
+            }
+            let marked = edges.iter().map(|&(v1, v2, _)| key(v1, v2)).collect();
+            plain_edges(out, &marked)?;
+        }
+        // edges labeled flow/capacity, the cut highlighted
+        Report::Flow { flows, cut, .. } => {
+            for v in 0..input.vertex_count() {
+                node(out, v, input.name(v))?;
+            }
+            let marked: HashSet<(usize, usize)> =
+                cut.iter().map(|&(from, to, _)| key(from, to)).collect();
+            for &(from, to, f, cap) in flows {
+                let mut attrs = format!(
+                    "label={}",
+                    dot_str(&format!("{}/{}", number(f), number(cap)))
+                );
+                if marked.contains(&key(from, to)) {
+                    attrs = format!("{}, {}", attrs, HIGHLIGHT);
+                }
+                writeln!(out, "{}", edge_line(from, to, &attrs))?;
+            }
+        }
+    }
+    writeln!(out, "}}")
+}
@@ -0,0 +1,44 @@
+use fibonacii_heap::{FibonacciHeap, IndexedBinaryHeap};
+use graph::directed_graph::DictetedGraph;
+use graph::undirected_graph::UndiGraph;
+use std::time::Instant;
+
+// usage: heap_bench [V] [E]
+fn main() {
+    let args: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|a| a.parse().ok())
+        .collect();
+    let v = args.first().copied().unwrap_or(100_000);
+    let e = args.get(1).copied().unwrap_or(10 * v);
+
+    let mut state = 2020u64;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+    let edges: Vec<(usize, usize, u64)> = (0..e)
+        .map(|_| (next() % v, next() % v, (next() % 1000) as u64))
+        .collect();
+    println!("V {} E {}", v, e);
+
+    let dg = DictetedGraph::build_graph(v, edges.clone());
+    let start = Instant::now();
+    let (binary, _) = dg.dijkstra_with::<IndexedBinaryHeap<_>>(0);
+    println!("dijkstra binary     {:?}", start.elapsed());
+    let start = Instant::now();
+    let (fib, _) = dg.dijkstra_with::<FibonacciHeap<_>>(0);
+    println!("dijkstra fibonacci  {:?}", start.elapsed());
+    assert_eq!(binary, fib);
+
+    let ug = UndiGraph::build_graph(v, edges);
+    let start = Instant::now();
+    let binary = ug.prim_msf_with::<IndexedBinaryHeap<_>>();
+    println!("prim binary         {:?}", start.elapsed());
+    let start = Instant::now();
+    let fib = ug.prim_msf_with::<FibonacciHeap<_>>();
+    println!("prim fibonacci      {:?}", start.elapsed());
+    assert_eq!(binary.total_weight(), fib.total_weight());
+}
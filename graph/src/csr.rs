@@ -0,0 +1,218 @@
+use crate::directed_graph::DictetedGraph;
+use crate::pagerank::{self, PageRank};
+use crate::search;
+use crate::traits::Graph;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+// Frozen compressed sparse row graph.
+// The out-edges of `v` are `targets[offsets[v]..offsets[v + 1]]`, with the
+// matching weights at the same positions in `weights`.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<W = f32> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W: Weight> CsrGraph<W> {
+    pub fn from_edges(v: usize, edges: &[(usize, usize, W)]) -> CsrGraph<W> {
+        let mut offsets = vec![0; v + 1];
+        for &(from, _, _) in edges {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..v {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        let mut weights = vec![W::zero(); edges.len()];
+        for &(from, to, weight) in edges {
+            targets[next[from]] = to;
+            weights[next[from]] = weight;
+            next[from] += 1;
+        }
+
+        CsrGraph {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    #[allow(non_snake_case)]
+    pub fn E(&self) -> usize {
+        self.targets.len()
+    }
+    #[allow(non_snake_case)]
+    pub fn V(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    pub fn weights(&self, v: usize) -> &[W] {
+        &self.weights[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    pub fn iter_edge(&self, v: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.neighbors(v)
+            .iter()
+            .copied()
+            .zip(self.weights(v).iter().copied())
+    }
+
+    pub fn get_reverse(&self) -> CsrGraph<W> {
+        let mut edges = Vec::with_capacity(self.E());
+        for v in 0..self.V() {
+            for (u, w) in self.iter_edge(v) {
+                edges.push((u, v, w));
+            }
+        }
+        CsrGraph::from_edges(self.V(), &edges)
+    }
+}
+
+impl<W: Weight> From<&DictetedGraph<W>> for CsrGraph<W> {
+    fn from(g: &DictetedGraph<W>) -> Self {
+        let mut edges = Vec::with_capacity(g.E());
+        for v in g.iter() {
+            for e in v.borrow().iter_edge() {
+                edges.push((e.from(), e.to(), e.weight()));
+            }
+        }
+        CsrGraph::from_edges(g.V(), &edges)
+    }
+}
+
+// Every undirected edge becomes a pair of arcs; a self-loop is stored once.
+impl<W: Weight> From<&UndiGraph<W>> for CsrGraph<W> {
+    fn from(g: &UndiGraph<W>) -> Self {
+        let mut edges = Vec::with_capacity(2 * g.E());
+        for e in g.iter_edges() {
+            let (v1, v2) = e.ends();
+            edges.push((v1, v2, e.weight()));
+            if v1 != v2 {
+                edges.push((v2, v1, e.weight()));
+            }
+        }
+        CsrGraph::from_edges(g.V(), &edges)
+    }
+}
+
+// Every stored arc is an edge of its own, so a CSR built from an undirected
+// graph is seen as a symmetric directed one.
+impl<W: Weight> Graph for CsrGraph<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.V()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.E()
+    }
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, W),
+    {
+        for (u, w) in self.iter_edge(v) {
+            f(u, w);
+        }
+    }
+
+    fn neighbors(&self, v: usize) -> Vec<usize> {
+        CsrGraph::neighbors(self, v).to_vec()
+    }
+}
+
+impl<W: Weight> CsrGraph<W> {
+    // Depths are u32::MAX for unreachable vertices, as in `DictetedGraph`.
+    pub fn breadth_first_search(&self, source: usize) -> (Vec<u32>, Vec<Option<usize>>) {
+        search::breadth_first_search(self, source)
+    }
+
+    pub fn dijkstra(&self, source: usize) -> (Vec<W>, Vec<Option<usize>>) {
+        search::dijkstra(self, source)
+    }
+
+    // Components come out in reverse topological order.
+    pub fn stronge_connected_components(&self) -> Vec<Vec<usize>> {
+        search::strongly_connected_components(self)
+    }
+
+    pub fn pagerank(&self, damping: f64, tolerance: f64, max_iterations: usize) -> PageRank {
+        pagerank::pagerank(self, damping, tolerance, max_iterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> DictetedGraph<u32> {
+        let edges = vec![
+            (0, 1, 4),
+            (1, 2, 1),
+            (2, 0, 2),
+            (1, 3, 7),
+            (3, 4, 1),
+            (4, 3, 1),
+            (2, 4, 9),
+        ];
+        DictetedGraph::build_graph(6, edges)
+    }
+
+    #[test]
+    fn csr_matches_linked_graph() {
+        let mut g = sample();
+        let csr = CsrGraph::from(&g);
+        assert_eq!((csr.V(), csr.E()), (6, 7));
+
+        let (depth, _) = csr.breadth_first_search(0);
+        g.breadth_first_search(0);
+        for (v, d) in depth.iter().enumerate() {
+            assert_eq!(*d, g.get_depth(v));
+        }
+
+        let (distance, _) = csr.dijkstra(0);
+        for (v, d) in distance.iter().enumerate() {
+            let expected = g.dijkstra_shortest_path(0, v).unwrap_or(u32::MAX);
+            assert_eq!(*d, expected);
+        }
+
+        let mut scc = csr.stronge_connected_components();
+        scc.iter_mut().for_each(|c| c.sort_unstable());
+        scc.sort();
+        assert_eq!(scc, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn pagerank_matches_the_adjacency_lists() {
+        let g = sample();
+        let csr = CsrGraph::from(&g);
+        let rank = csr.pagerank(0.85, 1e-10, 1000);
+        assert!(rank.converged());
+        assert!((rank.scores().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank.score(3) > rank.score(5) && rank.score(4) > rank.score(5));
+        let lists = g.pagerank(0.85, 1e-10, 1000);
+        assert_eq!(rank.iterations(), lists.iterations());
+        assert!(rank
+            .scores()
+            .iter()
+            .zip(lists.scores())
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+}
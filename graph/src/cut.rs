@@ -0,0 +1,352 @@
+// Minimum cuts of undirected graphs: the global minimum cut by Stoer-Wagner,
+// which needs no source or sink, and the Gomory-Hu tree, which holds the
+// minimum s-t cut of every pair of vertices in V - 1 edges. Edge weights are
+// the capacities and must be non-negative.
+use std::cmp::Ordering;
+
+use crate::flow;
+use crate::traits::Graph;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+// A cut as one of its sides; `side()` and `other_side()` are both non-empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCut<W> {
+    weight: W,
+    side: Vec<bool>,
+}
+
+impl<W: Weight> MinCut<W> {
+    // Total weight of the edges crossing the cut.
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+
+    pub fn side(&self) -> Vec<usize> {
+        (0..self.side.len()).filter(|&v| self.side[v]).collect()
+    }
+
+    pub fn other_side(&self) -> Vec<usize> {
+        (0..self.side.len()).filter(|&v| !self.side[v]).collect()
+    }
+
+    pub fn contains(&self, v: usize) -> bool {
+        self.side[v]
+    }
+
+    pub fn separates(&self, u: usize, v: usize) -> bool {
+        self.side[u] != self.side[v]
+    }
+
+    // The edges of `g` crossing the cut, listed from `side()`.
+    pub fn crossing_edges<G>(&self, g: &G) -> Vec<(usize, usize, W)>
+    where
+        G: Graph<Weight = W> + ?Sized,
+    {
+        g.edges()
+            .into_iter()
+            .filter(|&(from, to, _)| self.separates(from, to))
+            .map(|(from, to, w)| {
+                if self.side[from] {
+                    (from, to, w)
+                } else {
+                    (to, from, w)
+                }
+            })
+            .collect()
+    }
+}
+
+fn check_capacities<G: Graph + ?Sized>(g: &G) {
+    assert!(!g.is_directed(), "minimum cuts need an undirected graph");
+    assert!(
+        g.edges()
+            .iter()
+            .all(|e| e.2.cmp_weight(&G::Weight::zero()) != Ordering::Less),
+        "edge weights must be non-negative"
+    );
+}
+
+// The lightest cut splitting the vertices into two non-empty sides; None
+// with fewer than two vertices. A disconnected graph has a cut of weight
+// zero. O(V^3) on an adjacency matrix, parallel edges added up.
+//
+// Each phase grows a set from one vertex, always adding the vertex most
+// tightly connected to it. The last vertex added, alone, is a minimum cut
+// between it and the one before, so either that is the answer or the two
+// can be merged; V - 1 phases leave one vertex.
+pub fn stoer_wagner<G>(g: &G) -> Option<MinCut<G::Weight>>
+where
+    G: Graph + ?Sized,
+{
+    check_capacities(g);
+    let n = g.vertex_count();
+    if n < 2 {
+        return None;
+    }
+    let mut w = vec![vec![G::Weight::zero(); n]; n];
+    for (from, to, cap) in g.edges() {
+        if from != to {
+            w[from][to] = w[from][to].saturating_plus(cap);
+            w[to][from] = w[from][to];
+        }
+    }
+
+    // members[v] are the vertices merged into v
+    let mut members: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best: Option<(G::Weight, Vec<usize>)> = None;
+    while active.len() > 1 {
+        let mut added = vec![false; n];
+        let mut key = vec![G::Weight::zero(); n];
+        let (mut prev, mut last) = (active[0], active[0]);
+        for _ in 0..active.len() {
+            let next = active
+                .iter()
+                .copied()
+                .filter(|&v| !added[v])
+                .max_by(|&a, &b| key[a].cmp_weight(&key[b]))
+                .unwrap();
+            added[next] = true;
+            prev = last;
+            last = next;
+            for &v in active.iter().filter(|&&v| !added[v]) {
+                key[v] = key[v].saturating_plus(w[next][v]);
+            }
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|b| key[last].cmp_weight(&b.0) == Ordering::Less)
+        {
+            best = Some((key[last], members[last].clone()));
+        }
+
+        let moved = std::mem::take(&mut members[last]);
+        members[prev].extend(moved);
+        for &v in active.iter() {
+            w[prev][v] = w[prev][v].saturating_plus(w[last][v]);
+            w[v][prev] = w[prev][v];
+        }
+        w[prev][prev] = G::Weight::zero();
+        active.retain(|&v| v != last);
+    }
+
+    let (weight, side_members) = best.unwrap();
+    let mut side = vec![false; n];
+    side_members.into_iter().for_each(|v| side[v] = true);
+    Some(MinCut { weight, side })
+}
+
+// Rooted at vertex 0: vertex v > 0 hangs from `parent[v]` by an edge of
+// weight `weight[v]`. The minimum s-t cut of the graph is the lightest edge
+// on the tree path from s to t, and removing that edge splits the tree into
+// the two sides of such a cut.
+#[derive(Debug, Clone)]
+pub struct GomoryHuTree<W> {
+    parent: Vec<usize>,
+    weight: Vec<W>,
+}
+
+impl<W: Weight> GomoryHuTree<W> {
+    pub fn vertex_count(&self) -> usize {
+        self.parent.len()
+    }
+
+    // (v, parent, weight) for every vertex but the root.
+    pub fn edges(&self) -> Vec<(usize, usize, W)> {
+        (1..self.parent.len())
+            .map(|v| (v, self.parent[v], self.weight[v]))
+            .collect()
+    }
+
+    fn depth(&self, mut v: usize) -> usize {
+        let mut depth = 0;
+        while v != 0 {
+            v = self.parent[v];
+            depth += 1;
+        }
+        depth
+    }
+
+    // The vertex whose parent edge is the lightest on the path from u to v.
+    fn lightest_on_path(&self, mut u: usize, mut v: usize) -> usize {
+        let (mut du, mut dv) = (self.depth(u), self.depth(v));
+        let mut best: Option<usize> = None;
+        let take = |x: usize, best: &mut Option<usize>| {
+            if best.is_none_or(|b| self.weight[x].cmp_weight(&self.weight[b]) == Ordering::Less) {
+                *best = Some(x);
+            }
+        };
+        while u != v {
+            if du >= dv {
+                take(u, &mut best);
+                u = self.parent[u];
+                du -= 1;
+            } else {
+                take(v, &mut best);
+                v = self.parent[v];
+                dv -= 1;
+            }
+        }
+        best.unwrap()
+    }
+
+    // The minimum cut value between u and v, which must differ.
+    pub fn min_cut_value(&self, u: usize, v: usize) -> W {
+        assert_ne!(u, v, "a cut needs two different vertices");
+        self.weight[self.lightest_on_path(u, v)]
+    }
+
+    // A minimum cut between u and v, with u on `side()`.
+    pub fn min_cut(&self, u: usize, v: usize) -> MinCut<W> {
+        assert_ne!(u, v, "a cut needs two different vertices");
+        let x = self.lightest_on_path(u, v);
+        let below = |mut w: usize| loop {
+            if w == x {
+                return true;
+            }
+            if w == 0 {
+                return false;
+            }
+            w = self.parent[w];
+        };
+        let flip = !below(u);
+        MinCut {
+            weight: self.weight[x],
+            side: (0..self.parent.len()).map(|w| below(w) != flip).collect(),
+        }
+    }
+}
+
+// Gusfield's construction: V - 1 maximum flows by Edmonds-Karp on the
+// graph itself, with no contraction.
+pub fn gomory_hu_tree<G>(g: &G) -> GomoryHuTree<G::Weight>
+where
+    G: Graph + ?Sized,
+{
+    check_capacities(g);
+    let n = g.vertex_count();
+    let mut parent = vec![0; n];
+    let mut weight = vec![G::Weight::zero(); n];
+    for s in 1..n {
+        let t = parent[s];
+        let cut = flow::edmonds_karp(g, s, t);
+        weight[s] = cut.value();
+        for (v, p) in parent.iter_mut().enumerate() {
+            if v != s && *p == t && cut.on_source_side(v) {
+                *p = s;
+            }
+        }
+        if cut.on_source_side(parent[t]) {
+            parent[s] = parent[t];
+            parent[t] = s;
+            weight[s] = weight[t];
+            weight[t] = cut.value();
+        }
+    }
+    GomoryHuTree { parent, weight }
+}
+
+// Minimum cuts
+impl<W: Weight> UndiGraph<W> {
+    pub fn global_min_cut(&self) -> Option<MinCut<W>> {
+        stoer_wagner(self)
+    }
+
+    pub fn gomory_hu_tree(&self) -> GomoryHuTree<W> {
+        gomory_hu_tree(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_undirected;
+
+    fn random_graph(n: usize, m: usize, seed: u64) -> UndiGraph<i64> {
+        random_undirected(n, m, seed, |x| (x % 10) as i64)
+    }
+
+    fn cut_weight(g: &UndiGraph<i64>, side: &[usize]) -> i64 {
+        g.edges()
+            .iter()
+            .filter(|e| side.contains(&e.0) != side.contains(&e.1))
+            .map(|e| e.2)
+            .sum()
+    }
+
+    // every split, vertex 0 always on the first side
+    fn brute_force(g: &UndiGraph<i64>) -> i64 {
+        let n = g.V();
+        (0..(1u32 << (n - 1)) - 1)
+            .map(|mask| {
+                let side: Vec<usize> = (0..n)
+                    .filter(|&v| v == 0 || mask >> (v - 1) & 1 == 1)
+                    .collect();
+                cut_weight(g, &side)
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn stoer_wagner_matches_brute_force() {
+        for seed in 0..40 {
+            let g = random_graph(7, 10, seed);
+            let cut = g.global_min_cut().unwrap();
+            assert_eq!(cut.weight(), brute_force(&g));
+            assert_eq!(cut_weight(&g, &cut.side()), cut.weight());
+            assert!(!cut.side().is_empty() && !cut.other_side().is_empty());
+            let crossing: i64 = cut.crossing_edges(&g).iter().map(|e| e.2).sum();
+            assert_eq!(crossing, cut.weight());
+        }
+
+        // two triangles joined by one light edge
+        let edges = vec![
+            (0, 1, 3),
+            (1, 2, 3),
+            (2, 0, 3),
+            (3, 4, 3),
+            (4, 5, 3),
+            (5, 3, 3),
+            (2, 3, 1),
+        ];
+        let cut = UndiGraph::build_graph(6, edges).global_min_cut().unwrap();
+        assert_eq!(cut.weight(), 1);
+        assert!(cut.separates(0, 5) && !cut.separates(0, 2));
+        assert!(UndiGraph::<i64>::build_graph(1, vec![])
+            .global_min_cut()
+            .is_none());
+        assert_eq!(
+            UndiGraph::build_graph(3, vec![(0, 1, 4)])
+                .global_min_cut()
+                .unwrap()
+                .weight(),
+            0
+        );
+    }
+
+    #[test]
+    fn gomory_hu_answers_every_pair() {
+        for seed in 0..15 {
+            let g = random_graph(7, 12, seed);
+            let tree = g.gomory_hu_tree();
+            assert_eq!(tree.edges().len(), 6);
+            for u in 0..7 {
+                for v in 0..7 {
+                    if u == v {
+                        continue;
+                    }
+                    let value = flow::edmonds_karp(&g, u, v).value();
+                    assert_eq!(tree.min_cut_value(u, v), value);
+                    let cut = tree.min_cut(u, v);
+                    assert!(cut.contains(u) && !cut.contains(v));
+                    assert_eq!(cut_weight(&g, &cut.side()), value);
+                }
+            }
+            let lightest = tree.edges().iter().map(|e| e.2).min().unwrap();
+            assert_eq!(lightest, g.global_min_cut().unwrap().weight());
+        }
+    }
+}
@@ -0,0 +1,277 @@
+// Optimal cycles of directed graphs: Karp's minimum mean-weight cycle
+// (CLRS problem 24-5) and the minimum cost-to-time ratio cycle, found by a
+// parametric search over Bellman-Ford.
+use std::cmp::Ordering;
+
+use crate::directed_graph::DictetedGraph;
+use crate::search;
+use crate::traits::Graph;
+use crate::weight::Weight;
+
+// Splits a closed walk into simple cycles and keeps the one with the least
+// `score`. The walk is a vertex sequence in edge order.
+fn best_cycle_in_walk<F>(walk: &[usize], score: F) -> Vec<usize>
+where
+    F: Fn(&[usize]) -> f64,
+{
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    let mut stack: Vec<usize> = vec![];
+    for &v in walk {
+        if let Some(pos) = stack.iter().position(|&u| u == v) {
+            let cycle = stack.split_off(pos);
+            let s = score(&cycle);
+            if best.as_ref().is_none_or(|b| s < b.0) {
+                best = Some((s, cycle));
+            }
+        }
+        stack.push(v);
+    }
+    best.unwrap().1
+}
+
+// Mean weight of a cycle, each step over its lightest edge.
+fn mean_weight<G: Graph + ?Sized>(g: &G, cycle: &[usize]) -> f64 {
+    let total: f64 = (0..cycle.len())
+        .map(|i| {
+            let (from, to) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            g.edge_weight(from, to).unwrap().to_f64()
+        })
+        .sum();
+    total / cycle.len() as f64
+}
+
+// The least mean edge weight over all cycles, and a cycle attaining it
+// listed in edge order; None if the graph is acyclic. O(VE) time and
+// O(V^2) space.
+//
+// d[k][v] is the lightest walk of exactly k edges ending at v, starting
+// anywhere; the minimum mean is min over v of max over k of
+// (d[n][v] - d[k][v]) / (n - k), and the n-edge walk behind the minimizing
+// v closes a cycle of that mean.
+pub fn min_mean_cycle<G>(g: &G) -> Option<(f64, Vec<usize>)>
+where
+    G: Graph + ?Sized,
+{
+    assert!(g.is_directed(), "min_mean_cycle needs a directed graph");
+    let n = g.vertex_count();
+    let mut d = vec![vec![G::Weight::infinity(); n]; n + 1];
+    let mut parent = vec![vec![usize::MAX; n]; n + 1];
+    d[0].iter_mut().for_each(|w| *w = G::Weight::zero());
+
+    for k in 1..=n {
+        let (done, rest) = d.split_at_mut(k);
+        let (prev, cur) = (&done[k - 1], &mut rest[0]);
+        for (u, &reach) in prev.iter().enumerate() {
+            if reach.is_infinite() {
+                continue;
+            }
+            g.for_each_neighbor(u, |v, w| {
+                let through = reach.saturating_plus(w);
+                if through.cmp_weight(&cur[v]) == Ordering::Less {
+                    cur[v] = through;
+                    parent[k][v] = u;
+                }
+            });
+        }
+    }
+
+    let mut best: Option<(f64, usize)> = None;
+    for (v, last) in d[n].iter().enumerate() {
+        if last.is_infinite() {
+            continue;
+        }
+        let worst = (0..n)
+            .filter(|&k| !d[k][v].is_infinite())
+            .map(|k| (last.to_f64() - d[k][v].to_f64()) / (n - k) as f64)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if best.is_none_or(|b| worst < b.0) {
+            best = Some((worst, v));
+        }
+    }
+    let (mean, end) = best?;
+
+    let mut walk = vec![end];
+    let mut v = end;
+    for k in (1..=n).rev() {
+        v = parent[k][v];
+        walk.push(v);
+    }
+    walk.reverse();
+    let cycle = best_cycle_in_walk(&walk, |c| mean_weight(g, c));
+    Some((mean, cycle))
+}
+
+// The least total-cost / total-time ratio over all cycles, within
+// `tolerance`, and a cycle attaining it in edge order; None if the graph
+// is acyclic. Edge weights are the costs and `time(from, to, cost)` gives
+// each edge's time, which must be positive.
+//
+// A cycle has ratio below `lambda` exactly when it is negative under the
+// costs `cost - lambda * time`, so Bellman-Ford can decide which side of
+// the optimum `lambda` is on, and a bisection closes in on it.
+pub fn min_ratio_cycle<G, F>(g: &G, time: F, tolerance: f64) -> Option<(f64, Vec<usize>)>
+where
+    G: Graph + ?Sized,
+    F: Fn(usize, usize, G::Weight) -> f64,
+{
+    assert!(g.is_directed(), "min_ratio_cycle needs a directed graph");
+    let n = g.vertex_count();
+    let edges: Vec<(usize, usize, f64, f64)> = g
+        .edges()
+        .into_iter()
+        .map(|(from, to, w)| (from, to, w.to_f64(), time(from, to, w)))
+        .collect();
+    assert!(
+        edges.iter().all(|e| e.3 > 0.0),
+        "edge times must be positive"
+    );
+
+    // every step over the edge that is cheapest at `lambda`
+    let ratio = |cycle: &[usize], lambda: f64| {
+        let (mut cost, mut total_time) = (0.0, 0.0);
+        for i in 0..cycle.len() {
+            let (from, to) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            let (c, t) = edges
+                .iter()
+                .filter(|e| e.0 == from && e.1 == to)
+                .map(|e| (e.2, e.3))
+                .min_by(|a, b| (a.0 - lambda * a.1).total_cmp(&(b.0 - lambda * b.1)))
+                .unwrap();
+            cost += c;
+            total_time += t;
+        }
+        cost / total_time
+    };
+    // a cycle whose ratio is below `lambda`; vertex n reaches every vertex
+    let below = |lambda: f64| {
+        let mut h = DictetedGraph::new(n + 1);
+        for &(from, to, c, t) in edges.iter() {
+            h.add_edge(from, to, c - lambda * t);
+        }
+        for v in 0..n {
+            h.add_edge(n, v, 0.0);
+        }
+        search::negative_cycle(&h, n).map(|c| (ratio(&c, lambda), c))
+    };
+
+    // a cycle's ratio is a mediant of its edges' ratios
+    let ratios = edges.iter().map(|e| e.2 / e.3);
+    let mut lo = ratios.clone().fold(f64::INFINITY, f64::min);
+    let hi = ratios.fold(f64::NEG_INFINITY, f64::max);
+    let mut best = below(hi + hi.abs().max(1.0))?;
+
+    while best.0 - lo > tolerance {
+        let mid = lo + (best.0 - lo) / 2.0;
+        match below(mid) {
+            Some(found) if found.0 < best.0 => best = found,
+            Some(_) => break,
+            None => lo = mid,
+        }
+    }
+    Some(best)
+}
+
+// optimal cycles
+impl<W: Weight> DictetedGraph<W> {
+    pub fn min_mean_cycle(&self) -> Option<(f64, Vec<usize>)> {
+        min_mean_cycle(self)
+    }
+
+    pub fn min_ratio_cycle<F>(&self, time: F, tolerance: f64) -> Option<(f64, Vec<usize>)>
+    where
+        F: Fn(usize, usize, W) -> f64,
+    {
+        min_ratio_cycle(self, time, tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_directed;
+
+    fn random_graph(n: usize, m: usize, seed: u64) -> DictetedGraph<i64> {
+        random_directed(n, m, seed, |x| (x % 41) as i64 - 20)
+    }
+
+    // every simple cycle through its smallest vertex, by DFS
+    fn all_cycles(g: &DictetedGraph<i64>) -> Vec<Vec<usize>> {
+        fn extend(g: &DictetedGraph<i64>, path: &mut Vec<usize>, res: &mut Vec<Vec<usize>>) {
+            let last = *path.last().unwrap();
+            for u in g.neighbors(last) {
+                if u == path[0] {
+                    res.push(path.clone());
+                } else if u > path[0] && !path.contains(&u) {
+                    path.push(u);
+                    extend(g, path, res);
+                    path.pop();
+                }
+            }
+        }
+        let mut res = vec![];
+        for v in 0..g.V() {
+            extend(g, &mut vec![v], &mut res);
+        }
+        res
+    }
+
+    #[test]
+    fn karp_matches_brute_force() {
+        for seed in 0..30 {
+            let g = random_graph(7, 12, seed);
+            let expected = all_cycles(&g)
+                .iter()
+                .map(|c| mean_weight(&g, c))
+                .fold(f64::INFINITY, f64::min);
+            match g.min_mean_cycle() {
+                None => assert!(expected.is_infinite()),
+                Some((mean, cycle)) => {
+                    assert!((mean - expected).abs() < 1e-9);
+                    assert!((mean_weight(&g, &cycle) - mean).abs() < 1e-9);
+                }
+            }
+        }
+
+        let g = DictetedGraph::build_graph(3, vec![(0, 1, 2), (1, 2, 2)]);
+        assert_eq!(g.min_mean_cycle(), None);
+        let edges = vec![(0, 1, 1), (1, 0, 5), (1, 2, 1), (2, 3, 2), (3, 2, 2)];
+        let (mean, mut cycle) = DictetedGraph::build_graph(4, edges)
+            .min_mean_cycle()
+            .unwrap();
+        cycle.sort();
+        assert_eq!((mean, cycle), (2.0, vec![2, 3]));
+    }
+
+    #[test]
+    fn ratio_cycle_matches_brute_force() {
+        // time is 1 + (from + to) % 3 for every edge
+        let time = |from: usize, to: usize, _| (1 + (from + to) % 3) as f64;
+        for seed in 0..20 {
+            let g = random_graph(6, 11, seed);
+            let expected = all_cycles(&g)
+                .iter()
+                .map(|c| {
+                    let pairs = (0..c.len()).map(|i| (c[i], c[(i + 1) % c.len()]));
+                    let (cost, t) = pairs.fold((0.0, 0.0), |(cost, t), (from, to)| {
+                        let w = Graph::edge_weight(&g, from, to).unwrap();
+                        (cost + w as f64, t + time(from, to, w))
+                    });
+                    cost / t
+                })
+                .fold(f64::INFINITY, f64::min);
+            match g.min_ratio_cycle(time, 1e-9) {
+                None => assert!(expected.is_infinite()),
+                Some((ratio, cycle)) => {
+                    assert!((ratio - expected).abs() < 1e-6, "{} {}", ratio, expected);
+                    assert!(!cycle.is_empty());
+                }
+            }
+        }
+
+        // with unit times the ratio is the mean
+        let g = random_graph(8, 16, 99);
+        let (mean, _) = g.min_mean_cycle().unwrap();
+        let (ratio, _) = g.min_ratio_cycle(|_, _, _| 1.0, 1e-9).unwrap();
+        assert!((mean - ratio).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,247 @@
+// Depth-first search as a stream of events (CLRS 22.3).
+// Every edge is reported once with its classification, so cycle detection,
+// topological sorting, SCCs and bridges can be written as plain consumers.
+
+use trace::Event;
+
+use crate::traits::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    Tree,
+    Back,
+    Forward,
+    Cross,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent {
+    Discover(usize),
+    Finish(usize),
+    Edge(usize, usize, EdgeKind),
+}
+
+impl EdgeKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            EdgeKind::Tree => "tree",
+            EdgeKind::Back => "back",
+            EdgeKind::Forward => "forward",
+            EdgeKind::Cross => "cross",
+        }
+    }
+}
+
+// For recording a search: discovering and finishing a vertex are its
+// color changes to gray and black.
+impl From<DfsEvent> for Event {
+    fn from(e: DfsEvent) -> Self {
+        match e {
+            DfsEvent::Discover(v) => Event::new("color")
+                .with("v", v)
+                .with("color", Color::Gray.name()),
+            DfsEvent::Finish(v) => Event::new("color")
+                .with("v", v)
+                .with("color", Color::Black.name()),
+            DfsEvent::Edge(u, v, kind) => Event::new("edge")
+                .with("u", u)
+                .with("v", v)
+                .with("kind", kind.name()),
+        }
+    }
+}
+
+pub struct DfsEvents {
+    adj: Vec<Vec<usize>>,
+    directed: bool,
+    color: Vec<Color>,
+    discover: Vec<u32>,
+    // the tree parent, and whether the edge back to it has been skipped yet
+    parent: Vec<Option<usize>>,
+    parent_skipped: Vec<bool>,
+    stack: Vec<(usize, usize)>,
+    roots: Vec<usize>,
+    next_root: usize,
+    time: u32,
+    pending: Option<DfsEvent>,
+}
+
+impl DfsEvents {
+    // Starts from each vertex of `roots` that is still undiscovered, in order.
+    pub(crate) fn new(adj: Vec<Vec<usize>>, directed: bool, roots: Vec<usize>) -> Self {
+        let n = adj.len();
+        DfsEvents {
+            adj,
+            directed,
+            color: vec![Color::White; n],
+            discover: vec![0; n],
+            parent: vec![None; n],
+            parent_skipped: vec![false; n],
+            stack: Vec::new(),
+            roots,
+            next_root: 0,
+            time: 0,
+            pending: None,
+        }
+    }
+
+    fn discover(&mut self, v: usize) -> DfsEvent {
+        self.time += 1;
+        self.discover[v] = self.time;
+        self.color[v] = Color::Gray;
+        self.stack.push((v, 0));
+        DfsEvent::Discover(v)
+    }
+}
+
+impl Iterator for DfsEvents {
+    type Item = DfsEvent;
+
+    fn next(&mut self) -> Option<DfsEvent> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        loop {
+            let (u, i) = match self.stack.last_mut() {
+                Some(top) => {
+                    top.1 += 1;
+                    (top.0, top.1 - 1)
+                }
+                None => {
+                    while self.next_root < self.roots.len() {
+                        let root = self.roots[self.next_root];
+                        self.next_root += 1;
+                        if self.color[root] == Color::White {
+                            return Some(self.discover(root));
+                        }
+                    }
+                    return None;
+                }
+            };
+
+            let v = match self.adj[u].get(i) {
+                Some(&next) => next,
+                None => {
+                    self.stack.pop();
+                    self.color[u] = Color::Black;
+                    self.time += 1;
+                    return Some(DfsEvent::Finish(u));
+                }
+            };
+
+            let kind = match self.color[v] {
+                Color::White => {
+                    self.parent[v] = Some(u);
+                    self.pending = Some(self.discover(v));
+                    EdgeKind::Tree
+                }
+                // An undirected edge is seen from both ends: skip the way back
+                // up the tree edge (once, so a parallel edge still counts as a
+                // back edge), and the second sighting of a back edge.
+                Color::Gray
+                    if !self.directed && self.parent[u] == Some(v) && !self.parent_skipped[u] =>
+                {
+                    self.parent_skipped[u] = true;
+                    continue;
+                }
+                Color::Gray => EdgeKind::Back,
+                Color::Black if !self.directed => continue,
+                Color::Black if self.discover[u] < self.discover[v] => EdgeKind::Forward,
+                Color::Black => EdgeKind::Cross,
+            };
+            return Some(DfsEvent::Edge(u, v, kind));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DictetedGraph;
+    use crate::undirected_graph::UndiGraph;
+
+    #[test]
+    fn directed_edges_are_classified() {
+        let edges = vec![(0, 1, 1), (1, 2, 1), (2, 0, 1), (0, 2, 1), (3, 1, 1)];
+        let g: DictetedGraph<i32> = DictetedGraph::build_graph(4, edges);
+        let events: Vec<DfsEvent> = g.dfs_events().collect();
+        assert_eq!(
+            events,
+            vec![
+                DfsEvent::Discover(0),
+                DfsEvent::Edge(0, 1, EdgeKind::Tree),
+                DfsEvent::Discover(1),
+                DfsEvent::Edge(1, 2, EdgeKind::Tree),
+                DfsEvent::Discover(2),
+                DfsEvent::Edge(2, 0, EdgeKind::Back),
+                DfsEvent::Finish(2),
+                DfsEvent::Finish(1),
+                DfsEvent::Edge(0, 2, EdgeKind::Forward),
+                DfsEvent::Finish(0),
+                DfsEvent::Discover(3),
+                DfsEvent::Edge(3, 1, EdgeKind::Cross),
+                DfsEvent::Finish(3),
+            ]
+        );
+        assert_eq!(g.dfs_events_from(1).count(), 10);
+
+        assert_eq!(g.find_cycle(), Some(vec![0, 1, 2]));
+        assert_eq!(g.topological_order(), None);
+        let mut scc = g.tarjan_scc();
+        scc.iter_mut().for_each(|c| c.sort_unstable());
+        scc.sort();
+        assert_eq!(scc, vec![vec![0, 1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn acyclic_graph_has_topological_order() {
+        let edges = vec![(0, 2, 1), (1, 2, 1), (2, 3, 1), (1, 4, 1), (4, 3, 1)];
+        let mut g: DictetedGraph<i32> = DictetedGraph::build_graph(5, edges.clone());
+        assert!(!g.has_loop());
+        assert_eq!(g.find_cycle(), None);
+
+        let order = g.topological_order().unwrap();
+        let mut position = vec![0; order.len()];
+        for (i, &v) in order.iter().enumerate() {
+            position[v] = i;
+        }
+        assert!(edges.iter().all(|&(u, v, _)| position[u] < position[v]));
+        assert_eq!(g.tarjan_scc().len(), 5);
+    }
+
+    #[test]
+    fn undirected_bridges() {
+        // two triangles joined by the bridge 2-3, a pendant 5-6 and a
+        // doubled edge 6-7
+        let edges = vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 0, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (4, 5, 1),
+            (5, 3, 1),
+            (5, 6, 1),
+            (6, 7, 1),
+            (7, 6, 1),
+        ];
+        let g: UndiGraph<i32> = UndiGraph::build_graph(9, edges);
+        assert!(g
+            .dfs_events()
+            .all(|e| !matches!(e, DfsEvent::Edge(_, _, EdgeKind::Forward | EdgeKind::Cross))));
+        let back = g
+            .dfs_events()
+            .filter(|e| matches!(e, DfsEvent::Edge(_, _, EdgeKind::Back)))
+            .count();
+        assert_eq!(back, 3);
+
+        let mut bridges: Vec<(usize, usize)> = g
+            .bridges()
+            .into_iter()
+            .map(|(u, v)| (u.min(v), u.max(v)))
+            .collect();
+        bridges.sort_unstable();
+        assert_eq!(bridges, vec![(2, 3), (5, 6)]);
+    }
+}
@@ -1,747 +1,1162 @@
-// #![feature(total_cmp)]
-use std::cell::RefCell;
-use std::collections::{BinaryHeap, VecDeque};
-use std::rc::Rc;
-
-#[derive(Eq, PartialEq, Debug)]
-enum Color {
-    White,
-    Gray,
-    Black,
-}
-
-type Vptr = Rc<RefCell<Vertex>>;
-type Vlist = Vec<Rc<RefCell<Vertex>>>;
-pub struct Vertex {
-    idx: usize,
-    visited: u32,
-    color: Color,
-    discover_time: u32,
-    finish_time: u32,
-    depth: u32,
-    indegree: u32,
-    distance: f32,
-    ancestor: Option<Vptr>,
-    vlist: Vlist,
-    edges: Vec<Edge>,
-}
-
-impl std::fmt::Display for Vertex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ac = self
-            .ancestor
-            .as_ref()
-            .map_or("Nan".to_string(), |v| format!("{}", v.borrow().idx));
-        write!(
-            f,
-            "{}: {:?}, dt {}, ft {}, depth {}, in-degree {}, ancestor {}, edges {}",
-            self.idx,
-            self.color,
-            self.discover_time,
-            self.finish_time,
-            self.depth,
-            self.indegree,
-            ac,
-            self.vlist.len()
-        )
-    }
-}
-
-impl Vertex {
-    fn new(idx: usize) -> Self {
-        Vertex {
-            idx,
-            visited: 0,
-            color: Color::White,
-            discover_time: 0,
-            finish_time: 0,
-            depth: u32::MAX,
-            indegree: 0,
-            distance: f32::MAX,
-            ancestor: None,
-            vlist: Vec::<Vptr>::new(),
-            edges: Vec::new(),
-        }
-    }
-
-    pub fn copy(&self) -> Self {
-        Vertex {
-            idx: self.idx,
-            visited: self.visited,
-            color: Color::White,
-            discover_time: self.discover_time,
-            finish_time: self.finish_time,
-            depth: self.depth,
-            indegree: 0,
-            distance: f32::MAX,
-            ancestor: None,
-            vlist: Vec::<Vptr>::new(),
-            edges: Vec::new(),
-        }
-    }
-
-    // pub fn get_name(&self) -> usize {
-    //     self.idx
-    // }
-
-    pub fn new_vptr(idx: usize) -> Vptr {
-        Rc::new(RefCell::new(Vertex::new(idx)))
-    }
-
-    fn link(&mut self, v: Vptr, w: f32) {
-        v.borrow_mut().indegree += 1;
-        self.edges.push(Edge::new(self.idx, v.borrow().idx, w));
-        self.vlist.push(v);
-    }
-
-    fn reset_info(&mut self) {
-        self.visited = 0;
-        self.color = Color::White;
-        self.discover_time = 0;
-        self.finish_time = 0;
-        self.depth = u32::MAX;
-        self.ancestor = None;
-        self.distance = f32::MAX;
-    }
-
-    pub fn idx(&self) -> usize {
-        self.idx
-    }
-
-    pub fn get_distance(&self) -> f32 {
-        self.distance
-    }
-
-    #[allow(dead_code)]
-    fn is_visited(&self) -> bool {
-        self.visited >= 1
-    }
-
-    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex>>> {
-        self.vlist.iter()
-    }
-
-    pub fn iter_edge(&self) -> std::slice::Iter<'_, Edge> {
-        self.edges.iter()
-    }
-
-    pub fn dfs_traverse<F, P, L, T>(
-        &mut self,
-        pre: &mut F,
-        post: &mut P,
-        loop_handle: &mut L,
-        data: &mut T,
-    ) where
-        F: FnMut(&mut Vertex, &mut T),
-        P: FnMut(&mut Vertex, &mut T),
-        L: FnMut(),
-    {
-        if self.color != Color::White {
-            return;
-        }
-
-        self.color = Color::Gray;
-        {
-            pre(self, data);
-        }
-
-        for v in self.iter() {
-            match v.try_borrow_mut() {
-                Ok(mut u) => u.dfs_traverse(pre, post, loop_handle, data),
-                Err(_e) => loop_handle(),
-            }
-        }
-
-        self.color = Color::Black;
-        {
-            post(self, data);
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct Edge {
-    from: usize,
-    to: usize,
-    weight: f32,
-}
-
-impl Edge {
-    pub fn new(from: usize, to: usize, weight: f32) -> Self {
-        Edge { from, to, weight }
-    }
-}
-
-impl Eq for Edge {}
-
-impl Ord for Edge {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.weight > other.weight {
-            std::cmp::Ordering::Less
-        } else {
-            std::cmp::Ordering::Greater
-        }
-    }
-}
-
-impl PartialOrd for Edge {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for Edge {
-    fn eq(&self, other: &Self) -> bool {
-        self.from == other.from && self.to == other.to
-            || self.from == other.to && self.to == other.from
-    }
-}
-
-impl std::fmt::Display for Edge {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {} {:2.3}", self.from, self.to, self.weight)
-    }
-}
-
-pub struct DictetedGraph {
-    e: usize,
-    vertex_list: Vlist,
-}
-
-impl std::fmt::Display for DictetedGraph {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // write!(f, "({}, {})", self.x, self.y)
-        for v in self.iter() {
-            write!(f, "{}: ", v.borrow().idx)?;
-            for Edge {
-                from: _,
-                to,
-                weight,
-            } in v.borrow().iter_edge()
-            {
-                write!(f, "{} ({:2.3})", to, weight)?;
-            }
-            write!(f, "\n")?;
-        }
-
-        write!(f, "\n")
-    }
-}
-
-impl Clone for DictetedGraph {
-    fn clone(&self) -> Self {
-        let mut vertex_list = Vec::new();
-        for v in self.iter() {
-            vertex_list.push(Rc::new(RefCell::new(v.borrow().copy())));
-        }
-
-        let mut g = DictetedGraph {
-            e: 0,
-            vertex_list: vertex_list,
-        };
-
-        for v in self.iter() {
-            for Edge { from, to, weight } in v.borrow().iter_edge() {
-                g.add_edge(*from, *to, *weight);
-            }
-        }
-
-        g
-    }
-}
-
-impl DictetedGraph {
-    pub fn new(v: usize) -> DictetedGraph {
-        let e = 0;
-        let mut vertex_list = Vec::new();
-        for i in 0..v {
-            vertex_list.push(Vertex::new_vptr(i));
-        }
-        DictetedGraph { e, vertex_list }
-    }
-
-    pub fn build_graph(v: usize, edges: Vec<(usize, usize, f32)>) -> DictetedGraph {
-        let mut g = DictetedGraph::new(v);
-
-        for (from, to, weight) in edges {
-            g.add_edge(from, to, weight);
-        }
-
-        g
-    }
-
-    #[allow(non_snake_case)]
-    pub fn E(&self) -> usize {
-        self.e
-    }
-    #[allow(non_snake_case)]
-    pub fn V(&self) -> usize {
-        self.vertex_list.len()
-    }
-
-    pub fn get_vertex(&self, idx: usize) -> Vptr {
-        Rc::clone(&self.vertex_list[idx])
-    }
-
-    pub fn add_vertex(&mut self, v: Vptr) {
-        if v.borrow().idx >= self.V() {
-            self.vertex_list.push(v);
-        }
-    }
-
-    pub fn add_edge(&mut self, from_idx: usize, to_idx: usize, weight: f32) -> bool {
-        if from_idx == to_idx {
-            return false;
-        }
-
-        let from = self.get_vertex(from_idx);
-        let to = self.get_vertex(to_idx);
-
-        from.borrow_mut().link(to, weight);
-
-        self.e += 1;
-        true
-    }
-
-    pub fn get_reverse(&self) -> Self {
-        let mut vertex_list = Vec::new();
-        for v in self.iter() {
-            vertex_list.push(Rc::new(RefCell::new(v.borrow().copy())));
-        }
-
-        let mut rg = DictetedGraph {
-            e: 0,
-            vertex_list: vertex_list,
-        };
-
-        for v in self.iter() {
-            for Edge { from, to, weight } in v.borrow().iter_edge() {
-                rg.add_edge(*to, *from, *weight);
-            }
-        }
-
-        rg
-    }
-
-    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex>>> {
-        self.vertex_list.iter()
-    }
-
-    pub fn reset_vertexs_info(&mut self) {
-        for v in self.iter() {
-            v.borrow_mut().reset_info();
-        }
-    }
-
-    fn default_loop_handle() {}
-    fn default_dfs_func<T>(_v: &mut Vertex, _t: &mut T) {}
-}
-
-impl DictetedGraph {
-    pub fn breadth_first_search(&mut self, v_idx: usize) {
-        let mut que = std::collections::VecDeque::new();
-        let root = self.get_vertex(v_idx);
-        root.borrow_mut().color = Color::Gray;
-        root.borrow_mut().depth = 0;
-        que.push_back(root);
-
-        while !que.is_empty() {
-            let v = que.pop_front().unwrap();
-            let depth = v.borrow().depth;
-
-            for u in v.borrow().iter() {
-                if u.borrow().color == Color::White {
-                    u.borrow_mut().ancestor = Some(v.clone());
-                    u.borrow_mut().depth = depth + 1;
-                    u.borrow_mut().color = Color::Gray;
-                    que.push_back(u.clone());
-                }
-            }
-            v.borrow_mut().color = Color::Black;
-        }
-    }
-
-    pub fn query_depth(&mut self, from_idx: usize, to_idx: usize) -> u32 {
-        self.breadth_first_search(from_idx);
-        self.vertex_list[to_idx].borrow().depth
-    }
-
-    pub fn get_depth(&mut self, v_idx: usize) -> u32 {
-        self.vertex_list[v_idx].borrow().depth
-    }
-
-    pub fn get_ancestor(&self, v_idx: usize) -> Option<Vptr> {
-        self.vertex_list[v_idx].borrow().ancestor.clone()
-    }
-
-    pub fn get_min_indegree_vertex(&self) -> Vptr {
-        let mut min = self.vertex_list[0].borrow().indegree;
-        let mut idx = 0;
-        for v in self.iter().take(1) {
-            if v.borrow().indegree == 0 {
-                return Rc::clone(v);
-            }
-            if v.borrow().indegree < min {
-                idx = v.borrow().idx;
-                min = v.borrow().indegree;
-            }
-        }
-        self.get_vertex(idx)
-    }
-
-    pub fn depth_first_search(&mut self, start: Vptr) {
-        let mut time = 0u32;
-        dfs_helper(&start, &mut time);
-
-        for v in self.iter() {
-            dfs_helper(v, &mut time);
-        }
-
-        fn dfs_helper(v: &Vptr, time: &mut u32) {
-            if v.borrow().color != Color::White {
-                return;
-            }
-
-            *time += 1;
-            v.borrow_mut().discover_time = *time;
-            v.borrow_mut().color = Color::Gray;
-
-            for u in v
-                .borrow()
-                .iter()
-                .filter(|u| u.borrow().color == Color::White)
-            {
-                u.borrow_mut().ancestor = Some(Rc::clone(v));
-                dfs_helper(u, time);
-            }
-
-            v.borrow_mut().color = Color::Black;
-            *time += 1;
-            v.borrow_mut().finish_time = *time;
-        }
-    }
-
-    pub fn topological_sort(&mut self) -> Vec<(usize, u32)> {
-        let start = self.get_min_indegree_vertex();
-        self.depth_first_search(start);
-
-        let mut order = Vec::new();
-        for v in self.iter() {
-            order.push((v.borrow().idx, v.borrow().finish_time));
-        }
-
-        order.sort_by(|a, b| a.1.cmp(&b.1).reverse());
-
-        order
-    }
-
-    pub fn topological_sort_from(&mut self, idx: usize) -> Vec<(usize, u32)> {
-        let start = self.get_vertex(idx);
-        self.depth_first_search(start);
-
-        let mut order = Vec::new();
-        for v in self.iter() {
-            order.push((v.borrow().idx, v.borrow().finish_time));
-        }
-
-        order.sort_by(|a, b| a.1.cmp(&b.1).reverse());
-
-        order
-    }
-
-    pub fn dfs_traverse<F, P, L, T>(
-        &mut self,
-        pre: &mut F,
-        post: &mut P,
-        loop_handle: &mut L,
-        data: &mut T,
-    ) where
-        F: FnMut(&mut Vertex, &mut T),
-        P: FnMut(&mut Vertex, &mut T),
-        L: FnMut(),
-    {
-        self.reset_vertexs_info();
-        for v in self.iter().filter(|u| u.borrow().color == Color::White) {
-            v.borrow_mut().dfs_traverse(pre, post, loop_handle, data);
-        }
-    }
-
-    pub fn topological_sort_by_dfs(&mut self) -> VecDeque<(usize, u32)> {
-        let mut data = VecDeque::<(usize, u32)>::new();
-
-        // let mut pre = |_v, _time| {};
-        let mut pre = |v: &mut Vertex, discover_time: &mut u32| {
-            *discover_time += 1;
-            v.discover_time = *discover_time;
-        };
-        let mut post = |v: &mut Vertex, fin_time: &mut u32| {
-            *fin_time += 1;
-            v.finish_time = *fin_time;
-            data.push_front((v.idx, *fin_time));
-        };
-
-        let mut time = 0;
-        self.dfs_traverse(
-            &mut pre,
-            &mut post,
-            &mut DictetedGraph::default_loop_handle,
-            &mut time,
-        );
-
-        data
-    }
-
-    pub fn has_loop(&mut self) -> bool {
-        let mut cycle = false;
-        let mut loop_handle = || {
-            cycle = true;
-        };
-
-        let mut void = ();
-        self.dfs_traverse(
-            &mut DictetedGraph::default_dfs_func::<()>,
-            &mut DictetedGraph::default_dfs_func::<()>,
-            &mut loop_handle,
-            &mut void,
-        );
-
-        cycle
-    }
-
-    pub fn stronge_connected_components(&mut self) -> Vec<Vec<usize>> {
-        let topo_sort = self.topological_sort_by_dfs();
-
-        let rg = self.get_reverse();
-        let mut res = vec![];
-
-        for (idx, _) in topo_sort {
-            let mut connected = vec![];
-            let v = rg.get_vertex(idx);
-
-            let mut void = ();
-            let mut post = |v: &mut Vertex, _: &mut ()| {
-                connected.push(v.idx);
-            };
-            v.borrow_mut().dfs_traverse(
-                &mut DictetedGraph::default_dfs_func::<()>,
-                &mut post,
-                &mut DictetedGraph::default_loop_handle,
-                &mut void,
-            );
-            if !connected.is_empty() {
-                res.push(connected);
-            }
-        }
-
-        res
-    }
-}
-
-// shortest path algorithm
-impl DictetedGraph {
-    pub fn bellman_ford(&mut self, source_idx: usize) -> bool {
-        self.reset_vertexs_info();
-        // Initialized source vertex's distance
-        self.get_vertex(source_idx).borrow_mut().distance = 0.0;
-
-        for _ in 0..self.V() - 1 {
-            for v in self.iter() {
-                for e in v.borrow().iter_edge() {
-                    self.relax(e);
-                }
-            }
-        }
-
-        for v in self.iter() {
-            for Edge { from, to, weight } in v.borrow().iter_edge() {
-                let source = self.get_vertex(*from);
-                let sink = self.get_vertex(*to);
-                if sink.borrow().distance > source.borrow().distance + weight {
-                    return false;
-                }
-            }
-        }
-
-        true
-    }
-
-    // Only can apply to DAG
-    pub fn shortest_path_find_by_sort(&mut self, source_idx: usize) {
-        self.reset_vertexs_info();
-        let sort = self.topological_sort_by_dfs();
-        // println!("{:?}", sort);
-
-        self.reset_vertexs_info();
-        self.get_vertex(source_idx).borrow_mut().distance = 0.0;
-
-        let mut iter = sort.iter();
-        if let Some((source_idx, _)) = iter.find(|(idx, _)| idx == &source_idx) {
-            for e in self.get_vertex(*source_idx).borrow().iter_edge() {
-                self.relax(e);
-            }
-
-            for (idx, _) in iter {
-                // println!("{}", idx);
-                let v = self.get_vertex(*idx);
-                for e in v.borrow().iter_edge() {
-                    self.relax(e);
-                }
-            }
-        };
-    }
-
-    pub fn relax(&self, e: &Edge) {
-        let Edge { from, to, weight } = e;
-        let source = self.get_vertex(*from);
-        let sink = self.get_vertex(*to);
-
-        if sink.borrow().distance > source.borrow().distance + weight {
-            // println!(
-            //     "{}.d {}, {}.d {}, w {}",
-            //     from,
-            //     source.borrow().distance,
-            //     to,
-            //     sink.borrow().distance,
-            //     weight
-            // );
-            sink.borrow_mut().distance = source.borrow().distance + weight;
-            sink.borrow_mut().ancestor = Some(source);
-        }
-    }
-
-    pub fn get_shortest_path(&self, end_idx: usize) -> Vec<usize> {
-        let mut path = Vec::new();
-
-        let mut end_vertex = self.get_vertex(end_idx);
-        path.push(end_vertex.borrow().idx);
-
-        while let Some(v) = end_vertex.clone().borrow().ancestor.clone() {
-            end_vertex = v.clone();
-            path.push(v.borrow().idx);
-        }
-
-        path
-    }
-}
-
-mod record {
-    #[derive(Copy, Clone, PartialEq)]
-    pub struct State {
-        pub distance: f32,
-        pub vertex_idx: usize,
-    }
-
-    impl Eq for State {}
-    impl std::cmp::Ord for State {
-        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-            if self.distance < other.distance {
-                std::cmp::Ordering::Less
-            } else {
-                std::cmp::Ordering::Greater
-            }
-        }
-    }
-    impl PartialOrd for State {
-        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-            Some(self.cmp(other))
-        }
-    }
-}
-
-// dijkstra
-impl DictetedGraph {
-    pub fn dijkstra_shortest_path(&mut self, source_idx: usize, goal_idx: usize) -> Option<f32> {
-        let mut distance = vec![f32::MAX; self.V()];
-        let mut heap = BinaryHeap::new();
-
-        use record::State;
-        distance[source_idx] = 0.0;
-        heap.push(State {
-            distance: 0.0,
-            vertex_idx: source_idx,
-        });
-
-        while let Some(State {
-            distance: dist,
-            vertex_idx: v_idx,
-        }) = heap.pop()
-        {
-            if v_idx == goal_idx {
-                return Some(dist);
-            }
-            for Edge {
-                from: _,
-                to,
-                weight,
-            } in self.get_vertex(v_idx).borrow().iter_edge()
-            {
-                if distance[*to] > dist + *weight {
-                    distance[*to] = dist + *weight;
-                    heap.push(State {
-                        distance: distance[*to],
-                        vertex_idx: *to,
-                    })
-                }
-            }
-        }
-
-        None
-    }
-}
-
-pub fn parse_graph_from_stdio() -> Result<DictetedGraph, std::io::Error> {
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-
-    // println!("parse_graph_from_stdio:  {}", input);
-    let v = input.trim().parse::<usize>().unwrap();
-
-    input.clear();
-    std::io::stdin().read_line(&mut input)?;
-    let e = input.trim().parse::<usize>().unwrap();
-
-    let mut g = DictetedGraph::new(v);
-    for _ in 0..e {
-        input.clear();
-        std::io::stdin().read_line(&mut input)?;
-        // println!("{:?}", input.split_ascii_whitespace());
-        // let nums: Vec<&str> = input.trim().split(' ').collect();
-        let nums: Vec<&str> = input.split_ascii_whitespace().collect();
-        // println!("{:?}", nums);
-        // println!("{} {}", nums[0], nums[1]);
-        let weight = if nums.len() == 3 {
-            nums[2].trim().parse::<f32>().unwrap_or(1.0)
-        } else {
-            1.0
-        };
-        g.add_edge(
-            nums[0].trim().parse::<usize>().unwrap(),
-            nums[1].trim().parse::<usize>().unwrap(),
-            weight,
-        );
-    }
-
-    Ok(g)
-}
-
-// fn t1(a: &mut Vertex) -> usize {
-//     a.idx
-// }
-
-// fn t2(a: &mut Vertex) -> u32 {
-//     a.visited
-// }
-
-// fn t3<'r>(v: &'r mut Vertex) -> u32 {
-//     let a = t1(v);
-//     let b = t2(v);
-//     a as u32 + b
-// }
-
-// #[test]
-// fn test() {
-//     let mut v = Vertex::new(3);
-//     let a = t1(&mut v);
-//     let b = t2(&mut v);
-//     println!("{} {}", a, b);
-// }
+// #![feature(total_cmp)]
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::rc::Rc;
+
+use fibonacii_heap::{AddressableHeap, IndexedBinaryHeap};
+use trace::{NoTrace, Trace};
+
+use crate::dfs::{DfsEvent, DfsEvents, EdgeKind};
+use crate::search;
+use crate::subgraph::{self, FilteredGraph, InducedSubgraph};
+use crate::traits::{Color, Graph, GraphMut};
+use crate::weight::{OrdWeight, Weight};
+
+type Vptr<W> = Rc<RefCell<Vertex<W>>>;
+type Vlist<W> = Vec<Rc<RefCell<Vertex<W>>>>;
+pub struct Vertex<W = f32> {
+    idx: usize,
+    visited: u32,
+    color: Color,
+    discover_time: u32,
+    finish_time: u32,
+    depth: u32,
+    indegree: u32,
+    distance: W,
+    ancestor: Option<Vptr<W>>,
+    vlist: Vlist<W>,
+    edges: Vec<Edge<W>>,
+}
+
+impl<W: Weight> std::fmt::Display for Vertex<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ac = self
+            .ancestor
+            .as_ref()
+            .map_or("Nan".to_string(), |v| format!("{}", v.borrow().idx));
+        write!(
+            f,
+            "{}: {:?}, dt {}, ft {}, depth {}, in-degree {}, ancestor {}, edges {}",
+            self.idx,
+            self.color,
+            self.discover_time,
+            self.finish_time,
+            self.depth,
+            self.indegree,
+            ac,
+            self.vlist.len()
+        )
+    }
+}
+
+impl<W: Weight> Vertex<W> {
+    fn new(idx: usize) -> Self {
+        Vertex {
+            idx,
+            visited: 0,
+            color: Color::White,
+            discover_time: 0,
+            finish_time: 0,
+            depth: u32::MAX,
+            indegree: 0,
+            distance: W::infinity(),
+            ancestor: None,
+            vlist: Vec::<Vptr<W>>::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn copy(&self) -> Self {
+        Vertex {
+            idx: self.idx,
+            visited: self.visited,
+            color: Color::White,
+            discover_time: self.discover_time,
+            finish_time: self.finish_time,
+            depth: self.depth,
+            indegree: 0,
+            distance: W::infinity(),
+            ancestor: None,
+            vlist: Vec::<Vptr<W>>::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    // pub fn get_name(&self) -> usize {
+    //     self.idx
+    // }
+
+    pub fn new_vptr(idx: usize) -> Vptr<W> {
+        Rc::new(RefCell::new(Vertex::new(idx)))
+    }
+
+    fn link(&mut self, v: Vptr<W>, w: W) {
+        v.borrow_mut().indegree += 1;
+        self.edges.push(Edge::new(self.idx, v.borrow().idx, w));
+        self.vlist.push(v);
+    }
+
+    fn reset_info(&mut self) {
+        self.visited = 0;
+        self.color = Color::White;
+        self.discover_time = 0;
+        self.finish_time = 0;
+        self.depth = u32::MAX;
+        self.ancestor = None;
+        self.distance = W::infinity();
+    }
+
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn get_distance(&self) -> W {
+        self.distance
+    }
+
+    #[allow(dead_code)]
+    fn is_visited(&self) -> bool {
+        self.visited >= 1
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex<W>>>> {
+        self.vlist.iter()
+    }
+
+    pub fn iter_edge(&self) -> std::slice::Iter<'_, Edge<W>> {
+        self.edges.iter()
+    }
+
+    pub fn dfs_traverse<F, P, L, T>(
+        &mut self,
+        pre: &mut F,
+        post: &mut P,
+        loop_handle: &mut L,
+        data: &mut T,
+    ) where
+        F: FnMut(&mut Vertex<W>, &mut T),
+        P: FnMut(&mut Vertex<W>, &mut T),
+        L: FnMut(),
+    {
+        if self.color != Color::White {
+            return;
+        }
+
+        self.color = Color::Gray;
+        {
+            pre(self, data);
+        }
+
+        for v in self.iter() {
+            match v.try_borrow_mut() {
+                Ok(mut u) => u.dfs_traverse(pre, post, loop_handle, data),
+                Err(_e) => loop_handle(),
+            }
+        }
+
+        self.color = Color::Black;
+        {
+            post(self, data);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Edge<W = f32> {
+    from: usize,
+    to: usize,
+    weight: W,
+}
+
+impl<W: Weight> Edge<W> {
+    pub fn new(from: usize, to: usize, weight: W) -> Self {
+        Edge { from, to, weight }
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+}
+
+impl<W: Weight> Eq for Edge<W> {}
+
+// Reversed on weight so a `BinaryHeap<Edge>` pops the lightest edge first;
+// ties are broken on the endpoints. Equality looks at the endpoints only, so
+// equal edges may still order apart when their weights differ.
+impl<W: Weight> Ord for Edge<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let ends = |e: &Self| (e.from.min(e.to), e.from.max(e.to));
+        other
+            .weight
+            .cmp_weight(&self.weight)
+            .then_with(|| ends(other).cmp(&ends(self)))
+    }
+}
+
+impl<W: Weight> PartialOrd for Edge<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> PartialEq for Edge<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from && self.to == other.to
+            || self.from == other.to && self.to == other.from
+    }
+}
+
+impl<W: Weight> std::fmt::Display for Edge<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {:2.3}", self.from, self.to, self.weight)
+    }
+}
+
+pub struct DictetedGraph<W = f32> {
+    e: usize,
+    vertex_list: Vlist<W>,
+}
+
+impl<W: Weight> std::fmt::Display for DictetedGraph<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // write!(f, "({}, {})", self.x, self.y)
+        for v in self.iter() {
+            write!(f, "{}: ", v.borrow().idx)?;
+            for Edge {
+                from: _,
+                to,
+                weight,
+            } in v.borrow().iter_edge()
+            {
+                write!(f, "{} ({:2.3})", to, weight)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)
+    }
+}
+
+impl<W: Weight> Clone for DictetedGraph<W> {
+    fn clone(&self) -> Self {
+        let mut vertex_list = Vec::new();
+        for v in self.iter() {
+            vertex_list.push(Rc::new(RefCell::new(v.borrow().copy())));
+        }
+
+        let mut g = DictetedGraph { e: 0, vertex_list };
+
+        for v in self.iter() {
+            for Edge { from, to, weight } in v.borrow().iter_edge() {
+                g.add_edge(*from, *to, *weight);
+            }
+        }
+
+        g
+    }
+}
+
+impl<W: Weight> DictetedGraph<W> {
+    pub fn new(v: usize) -> DictetedGraph<W> {
+        let e = 0;
+        let mut vertex_list = Vec::new();
+        for i in 0..v {
+            vertex_list.push(Vertex::new_vptr(i));
+        }
+        DictetedGraph { e, vertex_list }
+    }
+
+    pub fn build_graph(v: usize, edges: Vec<(usize, usize, W)>) -> DictetedGraph<W> {
+        let mut g = DictetedGraph::new(v);
+
+        for (from, to, weight) in edges {
+            g.add_edge(from, to, weight);
+        }
+
+        g
+    }
+
+    #[allow(non_snake_case)]
+    pub fn E(&self) -> usize {
+        self.e
+    }
+    #[allow(non_snake_case)]
+    pub fn V(&self) -> usize {
+        self.vertex_list.len()
+    }
+
+    pub fn get_vertex(&self, idx: usize) -> Vptr<W> {
+        Rc::clone(&self.vertex_list[idx])
+    }
+
+    pub fn add_vertex(&mut self, v: Vptr<W>) {
+        if v.borrow().idx >= self.V() {
+            self.vertex_list.push(v);
+        }
+    }
+
+    // Self-loops and NaN weights are rejected.
+    pub fn add_edge(&mut self, from_idx: usize, to_idx: usize, weight: W) -> bool {
+        if from_idx == to_idx || !weight.is_valid() {
+            return false;
+        }
+
+        let from = self.get_vertex(from_idx);
+        let to = self.get_vertex(to_idx);
+
+        from.borrow_mut().link(to, weight);
+
+        self.e += 1;
+        true
+    }
+
+    pub fn get_reverse(&self) -> Self {
+        let mut vertex_list = Vec::new();
+        for v in self.iter() {
+            vertex_list.push(Rc::new(RefCell::new(v.borrow().copy())));
+        }
+
+        let mut rg = DictetedGraph { e: 0, vertex_list };
+
+        for v in self.iter() {
+            for Edge { from, to, weight } in v.borrow().iter_edge() {
+                rg.add_edge(*to, *from, *weight);
+            }
+        }
+
+        rg
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex<W>>>> {
+        self.vertex_list.iter()
+    }
+
+    pub fn reset_vertexs_info(&mut self) {
+        for v in self.iter() {
+            v.borrow_mut().reset_info();
+        }
+    }
+
+    fn default_loop_handle() {}
+}
+
+impl<W: Weight> Graph for DictetedGraph<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.V()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.E()
+    }
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, W),
+    {
+        for e in self.vertex_list[v].borrow().iter_edge() {
+            f(e.to, e.weight);
+        }
+    }
+}
+
+impl<W: Weight> GraphMut for DictetedGraph<W> {
+    fn push_vertex(&mut self) -> usize {
+        let idx = self.V();
+        self.add_vertex(Vertex::new_vptr(idx));
+        idx
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: W) -> bool {
+        DictetedGraph::add_edge(self, from, to, weight)
+    }
+
+    fn accepts_edge(&self, from: usize, to: usize, weight: W) -> bool {
+        from != to && weight.is_valid()
+    }
+}
+
+impl<W: Weight> DictetedGraph<W> {
+    // Records depth and ancestor on every vertex reached from `v_idx`.
+    pub fn breadth_first_search(&mut self, v_idx: usize) {
+        let (depth, ancestor) = search::breadth_first_search(self, v_idx);
+        for (v, d) in depth.into_iter().enumerate() {
+            if d == u32::MAX {
+                continue;
+            }
+            let mut v = self.vertex_list[v].borrow_mut();
+            v.depth = d;
+            v.ancestor = ancestor[v.idx].map(|u| self.get_vertex(u));
+            v.color = Color::Black;
+        }
+    }
+
+    pub fn query_depth(&mut self, from_idx: usize, to_idx: usize) -> u32 {
+        self.breadth_first_search(from_idx);
+        self.vertex_list[to_idx].borrow().depth
+    }
+
+    pub fn get_depth(&mut self, v_idx: usize) -> u32 {
+        self.vertex_list[v_idx].borrow().depth
+    }
+
+    pub fn get_ancestor(&self, v_idx: usize) -> Option<Vptr<W>> {
+        self.vertex_list[v_idx].borrow().ancestor.clone()
+    }
+
+    pub fn get_min_indegree_vertex(&self) -> Vptr<W> {
+        let mut min = self.vertex_list[0].borrow().indegree;
+        let mut idx = 0;
+        for v in self.iter().take(1) {
+            if v.borrow().indegree == 0 {
+                return Rc::clone(v);
+            }
+            if v.borrow().indegree < min {
+                idx = v.borrow().idx;
+                min = v.borrow().indegree;
+            }
+        }
+        self.get_vertex(idx)
+    }
+
+    // Discover and finish times, starting from `start` and then from every
+    // vertex left undiscovered.
+    pub fn depth_first_search(&mut self, start: Vptr<W>) {
+        let mut roots = vec![start.borrow().idx];
+        roots.extend(0..self.V());
+
+        let mut time = 0u32;
+        for event in search::dfs_events_with_roots(self, roots) {
+            match event {
+                DfsEvent::Discover(v) => {
+                    time += 1;
+                    self.vertex_list[v].borrow_mut().discover_time = time;
+                }
+                DfsEvent::Finish(v) => {
+                    time += 1;
+                    let mut v = self.vertex_list[v].borrow_mut();
+                    v.finish_time = time;
+                    v.color = Color::Black;
+                }
+                DfsEvent::Edge(u, v, EdgeKind::Tree) => {
+                    self.vertex_list[v].borrow_mut().ancestor = Some(self.get_vertex(u));
+                }
+                DfsEvent::Edge(..) => {}
+            }
+        }
+    }
+
+    pub fn topological_sort(&mut self) -> Vec<(usize, u32)> {
+        let start = self.get_min_indegree_vertex();
+        self.depth_first_search(start);
+
+        let mut order = Vec::new();
+        for v in self.iter() {
+            order.push((v.borrow().idx, v.borrow().finish_time));
+        }
+
+        order.sort_by(|a, b| a.1.cmp(&b.1).reverse());
+
+        order
+    }
+
+    pub fn topological_sort_from(&mut self, idx: usize) -> Vec<(usize, u32)> {
+        let start = self.get_vertex(idx);
+        self.depth_first_search(start);
+
+        let mut order = Vec::new();
+        for v in self.iter() {
+            order.push((v.borrow().idx, v.borrow().finish_time));
+        }
+
+        order.sort_by(|a, b| a.1.cmp(&b.1).reverse());
+
+        order
+    }
+
+    pub fn dfs_traverse<F, P, L, T>(
+        &mut self,
+        pre: &mut F,
+        post: &mut P,
+        loop_handle: &mut L,
+        data: &mut T,
+    ) where
+        F: FnMut(&mut Vertex<W>, &mut T),
+        P: FnMut(&mut Vertex<W>, &mut T),
+        L: FnMut(),
+    {
+        self.reset_vertexs_info();
+        for v in self.iter().filter(|u| u.borrow().color == Color::White) {
+            v.borrow_mut().dfs_traverse(pre, post, loop_handle, data);
+        }
+    }
+
+    pub fn topological_sort_by_dfs(&mut self) -> VecDeque<(usize, u32)> {
+        let mut data = VecDeque::<(usize, u32)>::new();
+
+        // let mut pre = |_v, _time| {};
+        let mut pre = |v: &mut Vertex<W>, discover_time: &mut u32| {
+            *discover_time += 1;
+            v.discover_time = *discover_time;
+        };
+        let mut post = |v: &mut Vertex<W>, fin_time: &mut u32| {
+            *fin_time += 1;
+            v.finish_time = *fin_time;
+            data.push_front((v.idx, *fin_time));
+        };
+
+        let mut time = 0;
+        self.dfs_traverse(
+            &mut pre,
+            &mut post,
+            &mut Self::default_loop_handle,
+            &mut time,
+        );
+
+        data
+    }
+
+    pub fn has_loop(&mut self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    // Components in topological order, as Kosaraju's two passes list them.
+    pub fn stronge_connected_components(&mut self) -> Vec<Vec<usize>> {
+        let mut res = search::strongly_connected_components(self);
+        res.reverse();
+        res
+    }
+}
+
+// Derived graphs, see `subgraph`.
+impl<W: Weight> DictetedGraph<W> {
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> InducedSubgraph<'_, Self> {
+        InducedSubgraph::new(self, vertices)
+    }
+
+    pub fn filtered<P>(&self, keep: P) -> FilteredGraph<'_, Self, P>
+    where
+        P: Fn(usize, usize, W) -> bool,
+    {
+        FilteredGraph::new(self, keep)
+    }
+
+    // The graph with the `down` edges taken out, e.g. for "what if these
+    // links fail" questions.
+    pub fn without_edges(
+        &self,
+        down: &[(usize, usize)],
+    ) -> FilteredGraph<'_, Self, impl Fn(usize, usize, W) -> bool> {
+        let down: HashSet<(usize, usize)> = down.iter().map(|&(u, v)| (u, v)).collect();
+        FilteredGraph::new(self, move |u, v, _| !down.contains(&(u, v)))
+    }
+
+    pub fn complement(&self) -> Self {
+        subgraph::complement(self, DictetedGraph::new(0))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        subgraph::union(self, other, DictetedGraph::new(0))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        subgraph::intersection(self, other, DictetedGraph::new(0))
+    }
+
+    // Also returns the edge each vertex of the line graph stands for.
+    pub fn line_graph(&self) -> (Self, Vec<(usize, usize)>) {
+        subgraph::line_graph(self, DictetedGraph::new(0))
+    }
+
+    pub fn cartesian_product(&self, other: &Self) -> Self {
+        subgraph::cartesian_product(self, other, DictetedGraph::new(0))
+    }
+}
+
+// DFS as an event stream, and the algorithms that only need to watch it
+impl<W: Weight> DictetedGraph<W> {
+    // Visits the whole graph, starting new trees in vertex order.
+    pub fn dfs_events(&self) -> DfsEvents {
+        search::dfs_events(self)
+    }
+
+    // Only the vertices reachable from `start`.
+    pub fn dfs_events_from(&self, start: usize) -> DfsEvents {
+        search::dfs_events_from(self, start)
+    }
+
+    // Vertices of the first cycle found, in edge order; the edge that closes
+    // it is the back edge from the last vertex to the first.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        let mut path = vec![];
+        for event in self.dfs_events() {
+            match event {
+                DfsEvent::Discover(v) => path.push(v),
+                DfsEvent::Finish(_) => {
+                    path.pop();
+                }
+                DfsEvent::Edge(_, v, EdgeKind::Back) => {
+                    let start = path.iter().rposition(|&u| u == v).unwrap();
+                    return Some(path.split_off(start));
+                }
+                DfsEvent::Edge(..) => {}
+            }
+        }
+        None
+    }
+
+    // Reverse finishing order; None if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut order = Vec::with_capacity(self.V());
+        for event in self.dfs_events() {
+            match event {
+                DfsEvent::Finish(v) => order.push(v),
+                DfsEvent::Edge(_, _, EdgeKind::Back) => return None,
+                _ => {}
+            }
+        }
+        order.reverse();
+        Some(order)
+    }
+
+    // Tarjan's algorithm; components come out in reverse topological order.
+    pub fn tarjan_scc(&self) -> Vec<Vec<usize>> {
+        search::strongly_connected_components(self)
+    }
+}
+
+// shortest path algorithm
+impl<W: Weight> DictetedGraph<W> {
+    // False if a negative cycle is reachable. The distances and ancestors
+    // are recorded on the vertices either way, as they stand after the
+    // V - 1 passes.
+    pub fn bellman_ford(&mut self, source_idx: usize) -> bool {
+        self.reset_vertexs_info();
+        let ((distance, ancestor), negative) =
+            search::bellman_ford_passes(self, source_idx, &mut NoTrace);
+
+        for v in self.iter() {
+            let mut v = v.borrow_mut();
+            v.distance = distance[v.idx];
+            v.ancestor = ancestor[v.idx].map(|u| self.get_vertex(u));
+        }
+        negative.is_none()
+    }
+
+    // Only can apply to DAG
+    pub fn shortest_path_find_by_sort(&mut self, source_idx: usize) {
+        self.shortest_path_find_by_sort_traced(source_idx, &mut NoTrace);
+    }
+
+    // Reports every successful "relax" in topological order.
+    pub fn shortest_path_find_by_sort_traced<T: Trace>(
+        &mut self,
+        source_idx: usize,
+        trace: &mut T,
+    ) {
+        self.reset_vertexs_info();
+        let sort = self.topological_sort_by_dfs();
+
+        self.reset_vertexs_info();
+        self.get_vertex(source_idx).borrow_mut().distance = W::zero();
+
+        let mut iter = sort.iter();
+        if let Some((source_idx, _)) = iter.find(|(idx, _)| idx == &source_idx) {
+            for e in self.get_vertex(*source_idx).borrow().iter_edge() {
+                self.relax_traced(e, trace);
+            }
+
+            for (idx, _) in iter {
+                let v = self.get_vertex(*idx);
+                for e in v.borrow().iter_edge() {
+                    self.relax_traced(e, trace);
+                }
+            }
+        };
+    }
+
+    pub fn relax(&self, e: &Edge<W>) {
+        self.relax_traced(e, &mut NoTrace);
+    }
+
+    pub fn relax_traced<T: Trace>(&self, e: &Edge<W>, trace: &mut T) {
+        let Edge { from, to, weight } = e;
+        let source = self.get_vertex(*from);
+        let sink = self.get_vertex(*to);
+
+        let through = source.borrow().distance.saturating_plus(*weight);
+        let old = sink.borrow().distance;
+        if through.cmp_weight(&old) == std::cmp::Ordering::Less {
+            trace.emit(|| search::relax_event(*from, *to, old, through));
+            sink.borrow_mut().distance = through;
+            sink.borrow_mut().ancestor = Some(source);
+        }
+    }
+
+    pub fn get_shortest_path(&self, end_idx: usize) -> Vec<usize> {
+        let mut path = Vec::new();
+
+        let mut end_vertex = self.get_vertex(end_idx);
+        path.push(end_vertex.borrow().idx);
+
+        while let Some(v) = end_vertex.clone().borrow().ancestor.clone() {
+            end_vertex = v.clone();
+            path.push(v.borrow().idx);
+        }
+
+        path
+    }
+}
+
+// dijkstra
+impl<W: Weight> DictetedGraph<W> {
+    pub fn dijkstra_shortest_path(&mut self, source_idx: usize, goal_idx: usize) -> Option<W> {
+        let removed = vec![false; self.V()];
+        let (distance, _) = self.dijkstra_avoiding::<IndexedBinaryHeap<_>>(
+            source_idx,
+            Some(goal_idx),
+            &removed,
+            &HashSet::new(),
+        );
+        Some(distance[goal_idx]).filter(|d| !d.is_infinite())
+    }
+
+    // Dijkstra over any addressable heap, e.g. `FibonacciHeap<_>` for the
+    // O(E + V log V) bound of CLRS 24.3.
+    pub fn dijkstra_with<H>(&self, source_idx: usize) -> (Vec<W>, Vec<Option<usize>>)
+    where
+        H: AddressableHeap<OrdWeight<W>>,
+    {
+        search::dijkstra_with::<_, H>(self, source_idx)
+    }
+}
+
+// single-source dijkstra and Yen's k shortest loopless paths
+impl<W: Weight> DictetedGraph<W> {
+    // Distances from `source` to every vertex plus the shortest-path tree.
+    pub fn dijkstra_single_source(&self, source_idx: usize) -> (Vec<W>, Vec<Option<usize>>) {
+        self.dijkstra_with::<IndexedBinaryHeap<_>>(source_idx)
+    }
+
+    // Dijkstra that ignores the `removed_vertex` vertices and the `removed_edge`
+    // arcs, and stops early once `goal_idx` is settled.
+    fn dijkstra_avoiding<H>(
+        &self,
+        source_idx: usize,
+        goal_idx: Option<usize>,
+        removed_vertex: &[bool],
+        removed_edge: &HashSet<(usize, usize)>,
+    ) -> (Vec<W>, Vec<Option<usize>>)
+    where
+        H: AddressableHeap<OrdWeight<W>>,
+    {
+        search::dijkstra_filtered::<_, H, _, _>(
+            self,
+            source_idx,
+            goal_idx,
+            |from, to| removed_vertex[to] || removed_edge.contains(&(from, to)),
+            &mut NoTrace,
+        )
+    }
+
+    pub fn path_weight(&self, path: &[usize]) -> Option<W> {
+        let mut total = W::zero();
+        for pair in path.windows(2) {
+            total = total.saturating_plus(self.edge_weight(pair[0], pair[1])?);
+        }
+        Some(total)
+    }
+
+    // Up to `k` simple paths from source to goal, cheapest first.
+    // Each path is listed from source to goal. Weights must be non-negative.
+    pub fn k_shortest_paths(
+        &self,
+        source_idx: usize,
+        goal_idx: usize,
+        k: usize,
+    ) -> Vec<(W, Vec<usize>)> {
+        let mut found: Vec<(W, Vec<usize>)> = Vec::new();
+        let mut candidates: Vec<(W, Vec<usize>)> = Vec::new();
+
+        let removed = vec![false; self.V()];
+        if let Some(first) =
+            self.shortest_path_avoiding(source_idx, goal_idx, &removed, &HashSet::new())
+        {
+            candidates.push(first);
+        }
+
+        while found.len() < k {
+            let best = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.0.cmp_weight(&b.0).then_with(|| a.1.cmp(&b.1)))
+                .map(|(i, _)| i);
+            let path = match best {
+                Some(i) => candidates.swap_remove(i),
+                None => break,
+            };
+            found.push(path);
+            if found.len() == k {
+                break;
+            }
+
+            let last = &found[found.len() - 1].1;
+            for j in 0..last.len() - 1 {
+                let root = &last[..=j];
+
+                let mut removed_edge = HashSet::new();
+                for (_, p) in found.iter() {
+                    if p.len() > j + 1 && &p[..=j] == root {
+                        removed_edge.insert((p[j], p[j + 1]));
+                    }
+                }
+                let mut removed_vertex = vec![false; self.V()];
+                for &v in &root[..j] {
+                    removed_vertex[v] = true;
+                }
+
+                if let Some((spur_weight, spur)) =
+                    self.shortest_path_avoiding(last[j], goal_idx, &removed_vertex, &removed_edge)
+                {
+                    let mut path = root[..j].to_vec();
+                    path.extend(spur);
+                    let root_weight = self.path_weight(root).unwrap();
+                    let total = root_weight.saturating_plus(spur_weight);
+                    if !candidates
+                        .iter()
+                        .chain(found.iter())
+                        .any(|(_, p)| *p == path)
+                    {
+                        candidates.push((total, path));
+                    }
+                }
+            }
+        }
+
+        found
+    }
+
+    fn shortest_path_avoiding(
+        &self,
+        source_idx: usize,
+        goal_idx: usize,
+        removed_vertex: &[bool],
+        removed_edge: &HashSet<(usize, usize)>,
+    ) -> Option<(W, Vec<usize>)> {
+        let (distance, ancestor) = self.dijkstra_avoiding::<IndexedBinaryHeap<_>>(
+            source_idx,
+            Some(goal_idx),
+            removed_vertex,
+            removed_edge,
+        );
+        if distance[goal_idx].is_infinite() {
+            return None;
+        }
+
+        let mut path = vec![goal_idx];
+        while let Some(v) = ancestor[*path.last().unwrap()] {
+            path.push(v);
+        }
+        path.reverse();
+        Some((distance[goal_idx], path))
+    }
+}
+
+// minimum spanning arborescence (Chu-Liu/Edmonds)
+impl<W: Weight> DictetedGraph<W> {
+    // The cheapest set of edges that reaches every vertex from `root`.
+    // Err(v) names a vertex that can't be reached from `root` at all.
+    pub fn min_spanning_arborescence(&self, root_idx: usize) -> Result<Vec<Edge<W>>, usize> {
+        let mut reached = vec![false; self.V()];
+        reached[root_idx] = true;
+        let mut stack = vec![root_idx];
+        while let Some(v) = stack.pop() {
+            for u in self.get_vertex(v).borrow().iter() {
+                let u_idx = u.borrow().idx;
+                if !reached[u_idx] {
+                    reached[u_idx] = true;
+                    stack.push(u_idx);
+                }
+            }
+        }
+        if let Some(v) = reached.iter().position(|r| !r) {
+            return Err(v);
+        }
+
+        let mut edges = Vec::new();
+        for v in self.iter() {
+            edges.extend(v.borrow().iter_edge().cloned());
+        }
+        let arcs: Vec<(usize, usize, W)> = edges.iter().map(|e| (e.from, e.to, e.weight)).collect();
+
+        let chosen = edmonds(self.V(), root_idx, &arcs);
+        Ok(chosen.into_iter().map(|i| edges[i].clone()).collect())
+    }
+}
+
+// Returns the indices of the arcs forming the arborescence. Every vertex must
+// be reachable from `root`.
+fn edmonds<W: Weight>(n: usize, root: usize, arcs: &[(usize, usize, W)]) -> Vec<usize> {
+    let mut min_in: Vec<Option<usize>> = vec![None; n];
+    for (i, &(from, to, weight)) in arcs.iter().enumerate() {
+        if from == to || to == root {
+            continue;
+        }
+        let lighter = match min_in[to] {
+            Some(j) => weight.cmp_weight(&arcs[j].2) == std::cmp::Ordering::Less,
+            None => true,
+        };
+        if lighter {
+            min_in[to] = Some(i);
+        }
+    }
+
+    // Walk the chosen in-arcs backwards looking for a cycle.
+    let mut comp = vec![usize::MAX; n];
+    let mut walked_from = vec![usize::MAX; n];
+    let mut count = 0;
+    let mut cycle = Vec::new();
+    for start in 0..n {
+        let mut v = start;
+        while v != root && walked_from[v] == usize::MAX && comp[v] == usize::MAX {
+            walked_from[v] = start;
+            v = arcs[min_in[v].unwrap()].0;
+        }
+        if v != root && walked_from[v] == start && comp[v] == usize::MAX && cycle.is_empty() {
+            let mut u = v;
+            loop {
+                cycle.push(u);
+                comp[u] = count;
+                u = arcs[min_in[u].unwrap()].0;
+                if u == v {
+                    break;
+                }
+            }
+            count += 1;
+        }
+    }
+
+    if cycle.is_empty() {
+        return (0..n)
+            .filter(|&v| v != root)
+            .map(|v| min_in[v].unwrap())
+            .collect();
+    }
+
+    // Contract the cycle into one vertex; arcs entering it are charged only
+    // the difference to the in-arc they would replace.
+    for c in comp.iter_mut() {
+        if *c == usize::MAX {
+            *c = count;
+            count += 1;
+        }
+    }
+    let in_cycle = |v: usize| comp[v] == comp[cycle[0]];
+
+    let mut contracted = Vec::new();
+    let mut origin = Vec::new();
+    for (i, &(from, to, weight)) in arcs.iter().enumerate() {
+        if comp[from] == comp[to] {
+            continue;
+        }
+        let weight = if in_cycle(to) {
+            weight.saturating_minus(arcs[min_in[to].unwrap()].2)
+        } else {
+            weight
+        };
+        contracted.push((comp[from], comp[to], weight));
+        origin.push(i);
+    }
+
+    let mut chosen: Vec<usize> = edmonds(count, comp[root], &contracted)
+        .into_iter()
+        .map(|i| origin[i])
+        .collect();
+    let entry = chosen
+        .iter()
+        .map(|&i| arcs[i].1)
+        .find(|&to| in_cycle(to))
+        .unwrap();
+    chosen.extend(
+        cycle
+            .iter()
+            .filter(|&&v| v != entry)
+            .map(|&v| min_in[v].unwrap()),
+    );
+    chosen
+}
+
+pub fn parse_graph_from_stdio<W>() -> Result<DictetedGraph<W>, std::io::Error>
+where
+    W: Weight + std::str::FromStr,
+{
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+
+    // println!("parse_graph_from_stdio:  {}", input);
+    let v = input.trim().parse::<usize>().unwrap();
+
+    input.clear();
+    std::io::stdin().read_line(&mut input)?;
+    let e = input.trim().parse::<usize>().unwrap();
+
+    let mut g = DictetedGraph::new(v);
+    for _ in 0..e {
+        input.clear();
+        std::io::stdin().read_line(&mut input)?;
+        // println!("{:?}", input.split_ascii_whitespace());
+        // let nums: Vec<&str> = input.trim().split(' ').collect();
+        let nums: Vec<&str> = input.split_ascii_whitespace().collect();
+        // println!("{:?}", nums);
+        // println!("{} {}", nums[0], nums[1]);
+        let weight = if nums.len() == 3 {
+            nums[2].trim().parse::<W>().unwrap_or_else(|_| W::one())
+        } else {
+            W::one()
+        };
+        if !weight.is_valid() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid weight `{}`", nums[2]),
+            ));
+        }
+        g.add_edge(
+            nums[0].trim().parse::<usize>().unwrap(),
+            nums[1].trim().parse::<usize>().unwrap(),
+            weight,
+        );
+    }
+
+    Ok(g)
+}
+
+// fn t1(a: &mut Vertex) -> usize {
+//     a.idx
+// }
+
+// fn t2(a: &mut Vertex) -> u32 {
+//     a.visited
+// }
+
+// fn t3<'r>(v: &'r mut Vertex) -> u32 {
+//     let a = t1(v);
+//     let b = t2(v);
+//     a as u32 + b
+// }
+
+// #[test]
+// fn test() {
+//     let mut v = Vertex::new(3);
+//     let a = t1(&mut v);
+//     let b = t2(&mut v);
+//     println!("{} {}", a, b);
+// }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_weights_give_exact_paths() {
+        let big = 1i64 << 60;
+        let edges = vec![(0, 1, big), (1, 2, 1), (0, 2, big + 2), (2, 3, -1)];
+        let mut g = DictetedGraph::build_graph(5, edges);
+
+        assert!(g.bellman_ford(0));
+        assert_eq!(g.get_vertex(2).borrow().get_distance(), big + 1);
+        assert_eq!(g.get_vertex(3).borrow().get_distance(), big);
+        assert!(g.get_vertex(4).borrow().get_distance().is_infinite());
+        assert_eq!(g.dijkstra_shortest_path(0, 2), Some(big + 1));
+        assert_eq!(g.dijkstra_shortest_path(0, 4), None);
+        let (fib, _) = g.dijkstra_with::<fibonacii_heap::FibonacciHeap<_>>(0);
+        assert_eq!(fib, g.dijkstra_single_source(0).0);
+    }
+
+    #[test]
+    fn bellman_ford_records_distances_on_a_negative_cycle() {
+        // 1 -> 2 -> 1 costs -1; 3 hangs off 0 and is untouched by it
+        let edges = vec![(0, 1, 1), (1, 2, 1), (2, 1, -2), (0, 3, 5)];
+        let mut g = DictetedGraph::build_graph(4, edges);
+        assert!(!g.bellman_ford(0));
+        assert_eq!(g.get_vertex(0).borrow().get_distance(), 0);
+        assert_eq!(g.get_vertex(3).borrow().get_distance(), 5);
+        assert_eq!(g.get_shortest_path(3), vec![3, 0]);
+        assert!(g.get_vertex(1).borrow().get_distance() < 1);
+    }
+
+    #[test]
+    fn yen_k_shortest_paths() {
+        let (c, d, e, f, g, h) = (0, 1, 2, 3, 4, 5);
+        let edges = vec![
+            (c, d, 3),
+            (c, e, 2),
+            (d, f, 4),
+            (e, d, 1),
+            (e, f, 2),
+            (e, g, 3),
+            (f, g, 2),
+            (f, h, 1),
+            (g, h, 2),
+        ];
+        let graph = DictetedGraph::build_graph(6, edges);
+
+        let paths = graph.k_shortest_paths(c, h, 3);
+        assert_eq!(
+            paths,
+            vec![
+                (5, vec![c, e, f, h]),
+                (7, vec![c, e, g, h]),
+                (8, vec![c, d, f, h]),
+            ]
+        );
+        assert_eq!(graph.k_shortest_paths(c, h, 100).len(), 7);
+        assert!(graph.k_shortest_paths(h, c, 2).is_empty());
+    }
+
+    #[test]
+    fn min_spanning_arborescence() {
+        let edges = vec![
+            (0, 1, 10),
+            (0, 2, 2),
+            (0, 3, 10),
+            (1, 0, 1),
+            (2, 1, 8),
+            (2, 3, 4),
+            (3, 1, 1),
+            (1, 2, 1),
+            (3, 2, 2),
+        ];
+        let graph = DictetedGraph::build_graph(4, edges);
+
+        let tree = graph.min_spanning_arborescence(0).unwrap();
+        let mut chosen: Vec<(usize, usize, i32)> =
+            tree.iter().map(|e| (e.from, e.to, e.weight)).collect();
+        chosen.sort_unstable();
+        assert_eq!(chosen, vec![(0, 2, 2), (2, 3, 4), (3, 1, 1)]);
+
+        let mut graph = graph;
+        graph.add_vertex(Vertex::new_vptr(4));
+        assert_eq!(graph.min_spanning_arborescence(0).err(), Some(4));
+    }
+
+    #[test]
+    fn nan_weights_are_rejected() {
+        let mut g = DictetedGraph::<f32>::new(2);
+        assert!(!g.add_edge(0, 1, f32::NAN));
+        assert!(g.add_edge(0, 1, 0.5));
+        assert_eq!(g.E(), 1);
+    }
+}
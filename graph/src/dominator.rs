@@ -0,0 +1,316 @@
+// Dominators of a flow graph with an entry vertex: d dominates v when every
+// path from the entry to v passes through d. Computed by Lengauer-Tarjan
+// with path compression, O(E log V). Post-dominators are the dominators of
+// the reverse graph from the exit.
+use crate::directed_graph::DictetedGraph;
+use crate::traits::Graph;
+use crate::weight::Weight;
+
+const NONE: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Dominators {
+    entry: usize,
+    idom: Vec<Option<usize>>,
+    reachable: Vec<bool>,
+    preds: Vec<Vec<usize>>,
+    // preorder interval of every vertex in the dominator tree
+    enter: Vec<usize>,
+    leave: Vec<usize>,
+}
+
+// Path compression over the DFS forest built so far, all in DFS numbers:
+// `eval(v)` is the vertex of least semidominator on the path from v up to,
+// not including, the root of its tree.
+struct Forest {
+    ancestor: Vec<usize>,
+    label: Vec<usize>,
+}
+
+impl Forest {
+    fn eval(&mut self, v: usize, semi: &[usize]) -> usize {
+        if self.ancestor[v] == NONE {
+            return v;
+        }
+        let mut path = vec![];
+        let mut x = v;
+        while self.ancestor[self.ancestor[x]] != NONE {
+            path.push(x);
+            x = self.ancestor[x];
+        }
+        while let Some(x) = path.pop() {
+            let a = self.ancestor[x];
+            if semi[self.label[a]] < semi[self.label[x]] {
+                self.label[x] = self.label[a];
+            }
+            self.ancestor[x] = self.ancestor[a];
+        }
+        self.label[v]
+    }
+}
+
+pub fn dominators<G>(g: &G, entry: usize) -> Dominators
+where
+    G: Graph + ?Sized,
+{
+    let n = g.vertex_count();
+    let mut preds = vec![vec![]; n];
+    for (from, to, _) in g.edges() {
+        preds[to].push(from);
+    }
+
+    // DFS numbering; `vertex[i]` has number i and `parent` is in numbers
+    let mut number = vec![NONE; n];
+    let mut vertex = vec![];
+    let mut parent = vec![];
+    let mut stack = vec![(entry, NONE)];
+    while let Some((v, p)) = stack.pop() {
+        if number[v] != NONE {
+            continue;
+        }
+        number[v] = vertex.len();
+        vertex.push(v);
+        parent.push(p);
+        let out = g.neighbors(v);
+        stack.extend(out.into_iter().rev().map(|u| (u, number[v])));
+    }
+
+    let count = vertex.len();
+    let mut semi: Vec<usize> = (0..count).collect();
+    let mut idom = vec![NONE; count];
+    let mut bucket = vec![vec![]; count];
+    let mut forest = Forest {
+        ancestor: vec![NONE; count],
+        label: (0..count).collect(),
+    };
+    for w in (1..count).rev() {
+        for &p in preds[vertex[w]].iter() {
+            if number[p] != NONE {
+                let u = forest.eval(number[p], &semi);
+                semi[w] = semi[w].min(semi[u]);
+            }
+        }
+        bucket[semi[w]].push(w);
+        forest.ancestor[w] = parent[w];
+        for v in std::mem::take(&mut bucket[parent[w]]) {
+            let u = forest.eval(v, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+        }
+    }
+    for w in 1..count {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut res = Dominators {
+        entry,
+        idom: vec![None; n],
+        reachable: vec![false; n],
+        preds,
+        enter: vec![0; n],
+        leave: vec![0; n],
+    };
+    for (i, &v) in vertex.iter().enumerate() {
+        res.reachable[v] = true;
+        if i > 0 {
+            res.idom[v] = Some(vertex[idom[i]]);
+        }
+    }
+
+    // number the dominator tree for O(1) `dominates`
+    let children = res.tree();
+    let mut clock = 0;
+    let mut stack = vec![(entry, false)];
+    while let Some((v, done)) = stack.pop() {
+        if done {
+            res.leave[v] = clock;
+            continue;
+        }
+        res.enter[v] = clock;
+        clock += 1;
+        stack.push((v, true));
+        stack.extend(children[v].iter().map(|&c| (c, false)));
+    }
+    res
+}
+
+impl Dominators {
+    pub fn entry(&self) -> usize {
+        self.entry
+    }
+
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.reachable[v]
+    }
+
+    // None for the entry and for vertices the entry doesn't reach.
+    pub fn immediate_dominator(&self, v: usize) -> Option<usize> {
+        self.idom[v]
+    }
+
+    // Every vertex dominates itself; nothing dominates an unreachable one.
+    pub fn dominates(&self, d: usize, v: usize) -> bool {
+        self.reachable[d]
+            && self.reachable[v]
+            && self.enter[d] <= self.enter[v]
+            && self.leave[v] <= self.leave[d]
+    }
+
+    pub fn strictly_dominates(&self, d: usize, v: usize) -> bool {
+        d != v && self.dominates(d, v)
+    }
+
+    // v's dominators from v itself up to the entry; empty if unreachable.
+    pub fn dominators_of(&self, v: usize) -> Vec<usize> {
+        if !self.reachable[v] {
+            return vec![];
+        }
+        let mut res = vec![v];
+        let mut x = v;
+        while let Some(d) = self.idom[x] {
+            res.push(d);
+            x = d;
+        }
+        res
+    }
+
+    // The dominator tree as children lists, in increasing order.
+    pub fn tree(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![vec![]; self.idom.len()];
+        for (v, d) in self.idom.iter().enumerate() {
+            if let Some(d) = d {
+                children[*d].push(v);
+            }
+        }
+        children
+    }
+
+    // DF(d): the vertices v where d's dominance ends, i.e. d dominates a
+    // predecessor of v but doesn't strictly dominate v; where SSA
+    // construction places phi-functions. By walking up from the
+    // predecessors of every join point, as Cooper, Harvey and Kennedy do.
+    pub fn dominance_frontiers(&self) -> Vec<Vec<usize>> {
+        let n = self.idom.len();
+        let mut frontier: Vec<Vec<usize>> = vec![vec![]; n];
+        for v in (0..n).filter(|&v| self.reachable[v]) {
+            let preds: Vec<usize> = self.preds[v]
+                .iter()
+                .copied()
+                .filter(|&p| self.reachable[p])
+                .collect();
+            if preds.len() < 2 && v != self.entry {
+                continue;
+            }
+            for p in preds {
+                let mut runner = Some(p);
+                while let Some(r) = runner.filter(|&r| Some(r) != self.idom[v]) {
+                    if frontier[r].last() != Some(&v) {
+                        frontier[r].push(v);
+                    }
+                    runner = self.idom[r];
+                }
+            }
+        }
+        frontier.iter_mut().for_each(|f| f.sort_unstable());
+        frontier
+    }
+}
+
+// Dominators
+impl<W: Weight> DictetedGraph<W> {
+    pub fn dominators(&self, entry: usize) -> Dominators {
+        dominators(self, entry)
+    }
+
+    // Post-dominators: the dominators of the reverse graph from `exit`. Its
+    // dominance frontiers are the reverse frontiers, i.e. control
+    // dependence.
+    pub fn post_dominators(&self, exit: usize) -> Dominators {
+        dominators(&self.get_reverse(), exit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lcg, random_edges};
+
+    // reachable from `entry` in g without `removed`
+    fn reaches(g: &DictetedGraph<i32>, entry: usize, removed: Option<usize>) -> Vec<bool> {
+        let mut seen = vec![false; g.V()];
+        let mut stack = vec![entry];
+        while let Some(v) = stack.pop() {
+            if Some(v) == removed || seen[v] {
+                continue;
+            }
+            seen[v] = true;
+            stack.extend(g.neighbors(v));
+        }
+        seen
+    }
+
+    #[test]
+    fn lengauer_tarjan_matches_definition() {
+        let mut next = lcg(17);
+        for _ in 0..60 {
+            let n = 9;
+            let g = DictetedGraph::build_graph(n, random_edges(n, 16, &mut next, |_| 1));
+            let dom = g.dominators(0);
+            let reachable = reaches(&g, 0, None);
+            for d in 0..n {
+                let without = reaches(&g, 0, Some(d));
+                for v in 0..n {
+                    let expected = reachable[v] && (d == v || d == 0 || !without[v]);
+                    assert_eq!(dom.dominates(d, v), expected, "{} dom {}", d, v);
+                }
+            }
+
+            // the definition of the frontier, for every pair
+            let frontier = dom.dominance_frontiers();
+            let reverse = g.get_reverse();
+            for (d, frontier) in frontier.iter().enumerate() {
+                let expected: Vec<usize> = (0..n)
+                    .filter(|&v| {
+                        reachable[v]
+                            && reverse.neighbors(v).iter().any(|&p| dom.dominates(d, p))
+                            && !dom.strictly_dominates(d, v)
+                    })
+                    .collect();
+                assert_eq!(*frontier, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn control_flow_graph() {
+        // 0 entry; 1 loop header; 2/3 the two arms of an if; 4 the join,
+        // which loops back to 1; 5 exit
+        let edges = vec![(0, 1), (1, 2), (1, 3), (2, 4), (3, 4), (4, 1), (1, 5)];
+        let g = DictetedGraph::build_graph(6, edges.into_iter().map(|(u, v)| (u, v, 1)).collect());
+        let dom = g.dominators(0);
+        let idoms: Vec<Option<usize>> = (0..6).map(|v| dom.immediate_dominator(v)).collect();
+        assert_eq!(
+            idoms,
+            vec![None, Some(0), Some(1), Some(1), Some(1), Some(1)]
+        );
+        assert_eq!(dom.tree()[1], vec![2, 3, 4, 5]);
+        assert_eq!(dom.dominators_of(4), vec![4, 1, 0]);
+        let frontier = dom.dominance_frontiers();
+        assert_eq!(frontier[2], vec![4]);
+        assert_eq!(frontier[4], vec![1]);
+        assert_eq!(frontier[1], vec![1]);
+
+        let post = g.post_dominators(5);
+        assert_eq!(post.immediate_dominator(2), Some(4));
+        assert_eq!(post.immediate_dominator(4), Some(1));
+        assert_eq!(post.immediate_dominator(0), Some(1));
+        // the arms are control dependent on the branch in 1
+        assert_eq!(post.dominance_frontiers()[2], vec![1]);
+
+        let unreachable = DictetedGraph::build_graph(3, vec![(0, 1, 1), (2, 1, 1)]).dominators(0);
+        assert!(!unreachable.is_reachable(2));
+        assert_eq!(unreachable.immediate_dominator(1), Some(0));
+        assert!(!unreachable.dominates(2, 1));
+    }
+}
@@ -0,0 +1,191 @@
+// Maximum flow by Edmonds-Karp (CLRS 26.2): Ford-Fulkerson that always
+// augments along a shortest residual path, found by BFS, so it finishes
+// after O(VE) augmentations. Edge weights are the capacities.
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use crate::traits::Graph;
+use crate::weight::Weight;
+
+#[derive(Debug, Clone)]
+pub struct MaxFlow<W> {
+    value: W,
+    flows: Vec<(usize, usize, W)>,
+    source_side: Vec<bool>,
+}
+
+impl<W: Weight> MaxFlow<W> {
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    // The flow on every edge of `g.edges()`, in that order. An undirected
+    // edge is oriented the way its flow goes.
+    pub fn flows(&self) -> &[(usize, usize, W)] {
+        &self.flows
+    }
+
+    // Whether `v` is on the source side of the minimum cut, i.e. still
+    // reachable from the source in the final residual network.
+    pub fn on_source_side(&self, v: usize) -> bool {
+        self.source_side[v]
+    }
+
+    // The saturated edges crossing the minimum cut; their capacities add up
+    // to `value()`.
+    pub fn min_cut<G>(&self, g: &G) -> Vec<(usize, usize, W)>
+    where
+        G: Graph<Weight = W> + ?Sized,
+    {
+        g.edges()
+            .into_iter()
+            .filter_map(|(from, to, cap)| {
+                if self.source_side[from] && !self.source_side[to] {
+                    Some((from, to, cap))
+                } else if !g.is_directed() && self.source_side[to] && !self.source_side[from] {
+                    Some((to, from, cap))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+fn is_positive<W: Weight>(w: W) -> bool {
+    w.cmp_weight(&W::zero()) == Ordering::Greater
+}
+
+pub fn edmonds_karp<G>(g: &G, source: usize, sink: usize) -> MaxFlow<G::Weight>
+where
+    G: Graph + ?Sized,
+{
+    let n = g.vertex_count();
+    let edges = g.edges();
+
+    // arc 2i is edge i, arc 2i + 1 its reverse; an undirected edge has
+    // capacity both ways
+    let mut head = vec![vec![]; n];
+    let mut to = Vec::with_capacity(2 * edges.len());
+    let mut residual = Vec::with_capacity(2 * edges.len());
+    for &(from, t, cap) in edges.iter() {
+        head[from].push(to.len());
+        to.push(t);
+        residual.push(cap);
+        head[t].push(to.len());
+        to.push(from);
+        residual.push(if g.is_directed() {
+            G::Weight::zero()
+        } else {
+            cap
+        });
+    }
+
+    let mut value = G::Weight::zero();
+    let mut reached = vec![false; n];
+    loop {
+        // BFS over arcs with residual capacity, remembering the arc used
+        reached.iter_mut().for_each(|r| *r = false);
+        let mut via: Vec<Option<usize>> = vec![None; n];
+        let mut que = VecDeque::new();
+        reached[source] = true;
+        que.push_back(source);
+        while let Some(v) = que.pop_front() {
+            for &a in head[v].iter() {
+                if !reached[to[a]] && is_positive(residual[a]) {
+                    reached[to[a]] = true;
+                    via[to[a]] = Some(a);
+                    que.push_back(to[a]);
+                }
+            }
+        }
+        if source == sink || !reached[sink] {
+            break;
+        }
+
+        let mut path = vec![];
+        let mut v = sink;
+        while let Some(a) = via[v] {
+            path.push(a);
+            v = to[a ^ 1];
+        }
+        let bottleneck = path
+            .iter()
+            .map(|&a| residual[a])
+            .fold(G::Weight::infinity(), |b, r| b.min_weight(r));
+        for &a in path.iter() {
+            residual[a] = residual[a].saturating_minus(bottleneck);
+            residual[a ^ 1] = residual[a ^ 1].saturating_plus(bottleneck);
+        }
+        value = value.saturating_plus(bottleneck);
+    }
+
+    let flows = edges
+        .iter()
+        .enumerate()
+        .map(|(i, &(from, t, cap))| {
+            let forward = cap.saturating_minus(residual[2 * i]);
+            if g.is_directed() || !is_positive(residual[2 * i].saturating_minus(cap)) {
+                (from, t, forward)
+            } else {
+                (t, from, residual[2 * i].saturating_minus(cap))
+            }
+        })
+        .collect();
+
+    MaxFlow {
+        value,
+        flows,
+        source_side: reached,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DictetedGraph;
+    use crate::undirected_graph::UndiGraph;
+
+    #[test]
+    fn clrs_figure_26_6() {
+        let edges = vec![
+            (0, 1, 16),
+            (0, 2, 13),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ];
+        let g = DictetedGraph::build_graph(6, edges);
+        let flow = edmonds_karp(&g, 0, 5);
+        assert_eq!(flow.value(), 23);
+
+        // conservation at every inner vertex, capacities respected
+        let mut net = vec![0i64; 6];
+        for (&(from, to, f), (_, _, cap)) in flow.flows().iter().zip(g.edges()) {
+            assert!(f <= cap);
+            net[from] -= f as i64;
+            net[to] += f as i64;
+        }
+        assert_eq!(net, vec![-23, 0, 0, 0, 0, 23]);
+
+        let cut = flow.min_cut(&g);
+        assert_eq!(cut.iter().map(|e| e.2).sum::<i32>(), 23);
+        assert!(flow.on_source_side(0) && !flow.on_source_side(5));
+        assert_eq!(edmonds_karp(&g, 5, 0).value(), 0);
+    }
+
+    #[test]
+    fn undirected_edges_carry_flow_either_way() {
+        let g = UndiGraph::build_graph(4, vec![(1, 0, 3.0), (2, 1, 2.0), (1, 3, 1.0), (3, 2, 5.0)]);
+        let flow = edmonds_karp(&g, 0, 2);
+        assert_eq!(flow.value(), 3.0);
+        assert!(flow.flows().contains(&(0, 1, 3.0)));
+        assert!(flow.flows().contains(&(1, 2, 2.0)));
+        assert!(flow.flows().contains(&(3, 2, 1.0)));
+        assert_eq!(flow.min_cut(&g), vec![(0, 1, 3.0)]);
+    }
+}
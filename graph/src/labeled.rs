@@ -0,0 +1,371 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::BufRead;
+
+use crate::directed_graph::DictetedGraph;
+use crate::search;
+use crate::traits::GraphMut;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+// Two-way mapping between labels of any `Hash + Eq` type and the dense
+// vertex ids 0..len() the algorithms work with.
+#[derive(Debug, Clone)]
+pub struct LabelMap<L> {
+    ids: HashMap<L, usize>,
+    labels: Vec<L>,
+}
+
+impl<L: Hash + Eq + Clone> LabelMap<L> {
+    pub fn new() -> Self {
+        LabelMap {
+            ids: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    // The id of `label`, handing out the next free one if it is new.
+    pub fn insert(&mut self, label: L) -> usize {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        id
+    }
+
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> &L {
+        &self.labels[id]
+    }
+
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    pub fn to_labels(&self, ids: &[usize]) -> Vec<&L> {
+        ids.iter().map(|&id| self.label(id)).collect()
+    }
+}
+
+impl<L: Hash + Eq + Clone> Default for LabelMap<L> {
+    fn default() -> Self {
+        LabelMap::new()
+    }
+}
+
+// A graph whose vertices are addressed by label. Vertex `i` of `graph`
+// is `labels.label(i)`; vertices only come in through the label map, so
+// the two never drift apart.
+#[derive(Debug, Clone)]
+pub struct LabeledGraph<G, L = String> {
+    graph: G,
+    labels: LabelMap<L>,
+}
+
+impl<G, L> LabeledGraph<G, L>
+where
+    G: GraphMut,
+    L: Hash + Eq + Clone,
+{
+    // `graph` must not have any vertices yet.
+    pub fn new(graph: G) -> Self {
+        assert_eq!(graph.vertex_count(), 0, "graph already has vertices");
+        LabeledGraph {
+            graph,
+            labels: LabelMap::new(),
+        }
+    }
+
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    pub fn labels(&self) -> &LabelMap<L> {
+        &self.labels
+    }
+
+    pub fn into_parts(self) -> (G, LabelMap<L>) {
+        (self.graph, self.labels)
+    }
+
+    pub fn id<Q>(&self, label: &Q) -> Option<usize>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.labels.id(label)
+    }
+
+    pub fn label(&self, id: usize) -> &L {
+        self.labels.label(id)
+    }
+
+    // Existing labels keep their vertex.
+    pub fn add_vertex(&mut self, label: L) -> usize {
+        let id = self.labels.insert(label);
+        if id == self.graph.vertex_count() {
+            self.graph.push_vertex();
+        }
+        id
+    }
+
+    // Missing endpoints are added first, but only if the graph accepts the
+    // edge; a rejected edge leaves the graph as it was.
+    pub fn add_edge_by_label<Q>(&mut self, from: &Q, to: &Q, weight: G::Weight) -> bool
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = L> + ?Sized,
+    {
+        // the ids the endpoints have, or will get
+        let next = self.labels.len();
+        let from_id = self.id(from).unwrap_or(next);
+        let to_id = match self.id(to) {
+            Some(id) => id,
+            None if from == to => from_id,
+            // a new `to` comes after a new `from`
+            None if from_id == next => next + 1,
+            None => next,
+        };
+        if !self.graph.accepts_edge(from_id, to_id, weight) {
+            return false;
+        }
+        let from = self.add_vertex(from.to_owned());
+        let to = self.add_vertex(to.to_owned());
+        self.graph.add_edge(from, to, weight)
+    }
+
+    pub fn path_labels(&self, path: &[usize]) -> Vec<&L> {
+        self.labels.to_labels(path)
+    }
+
+    pub fn group_labels(&self, groups: &[Vec<usize>]) -> Vec<Vec<&L>> {
+        groups.iter().map(|g| self.labels.to_labels(g)).collect()
+    }
+
+    pub fn edge_labels<I>(&self, edges: I) -> Vec<(&L, &L, G::Weight)>
+    where
+        I: IntoIterator<Item = (usize, usize, G::Weight)>,
+    {
+        edges
+            .into_iter()
+            .map(|(from, to, w)| (self.label(from), self.label(to), w))
+            .collect()
+    }
+
+    // Length and vertices, source first, of a shortest path between two
+    // labels; None if either label is unknown or `to` is unreachable.
+    pub fn shortest_path<Q>(&self, from: &Q, to: &Q) -> Option<(G::Weight, Vec<&L>)>
+    where
+        L: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (from, to) = (self.id(from)?, self.id(to)?);
+        let (distance, ancestor) = search::dijkstra(&self.graph, from);
+        if distance[to].is_infinite() {
+            return None;
+        }
+        let mut path = search::path_to(&ancestor, to);
+        path.reverse();
+        Some((distance[to], self.path_labels(&path)))
+    }
+}
+
+impl<W: Weight, L: Hash + Eq + Clone> LabeledGraph<DictetedGraph<W>, L> {
+    pub fn stronge_connected_components(&self) -> Vec<Vec<&L>> {
+        self.group_labels(&self.graph.tarjan_scc())
+    }
+
+    // None if the graph has a cycle.
+    pub fn topological_order(&self) -> Option<Vec<&L>> {
+        Some(self.path_labels(&self.graph.topological_order()?))
+    }
+}
+
+impl<W: Weight, L: Hash + Eq + Clone> LabeledGraph<UndiGraph<W>, L> {
+    pub fn kerskal_msf(&self) -> Vec<(&L, &L, W)> {
+        let forest = self.graph.kerskal_msf();
+        self.edge_labels(forest.edges().map(|e| {
+            let (v1, v2) = e.ends();
+            (v1, v2, e.weight())
+        }))
+    }
+}
+
+fn invalid_line(line: usize, msg: String) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, msg),
+    )
+}
+
+// One edge per line, "from to [weight]", e.g. "serde syn 3.5"; the weight
+// defaults to one. A line with a single label adds an isolated vertex.
+// Blank lines and lines starting with '#' are skipped. An edge the graph
+// turns down, such as a self-loop in a `DictetedGraph`, is an error.
+pub fn parse_labeled_graph<G, R>(graph: G, reader: R) -> Result<LabeledGraph<G>, std::io::Error>
+where
+    G: GraphMut,
+    G::Weight: std::str::FromStr,
+    R: BufRead,
+{
+    let mut g = LabeledGraph::new(graph);
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        match fields[..] {
+            [] => {}
+            [first, ..] if first.starts_with('#') => {}
+            [label] => {
+                g.add_vertex(label.to_string());
+            }
+            [from, to] | [from, to, _] => {
+                let weight = match fields.get(2) {
+                    Some(weight) => weight
+                        .parse::<G::Weight>()
+                        .ok()
+                        .filter(|w| w.is_valid())
+                        .ok_or_else(|| {
+                            invalid_line(i + 1, format!("invalid weight `{}`", weight))
+                        })?,
+                    None => G::Weight::one(),
+                };
+                if !g.add_edge_by_label(from, to, weight) {
+                    return Err(invalid_line(i + 1, format!("edge `{}` rejected", line)));
+                }
+            }
+            _ => {
+                return Err(invalid_line(
+                    i + 1,
+                    format!("too many fields in `{}`", line),
+                ))
+            }
+        }
+    }
+    Ok(g)
+}
+
+pub fn parse_labeled_graph_from_stdio<G>(graph: G) -> Result<LabeledGraph<G>, std::io::Error>
+where
+    G: GraphMut,
+    G::Weight: std::str::FromStr,
+{
+    parse_labeled_graph(graph, std::io::stdin().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Graph;
+
+    const PACKAGES: &str = "\
+# crate depends-on weight
+app serde 2
+app log
+serde serde_derive 1.5
+serde_derive syn 3.5
+syn proc-macro2
+proc-macro2 unicode-ident
+quote proc-macro2
+syn quote
+standalone
+";
+
+    #[test]
+    fn parses_named_dependency_graph() {
+        let g = parse_labeled_graph(DictetedGraph::<f64>::new(0), PACKAGES.as_bytes()).unwrap();
+        assert_eq!((g.graph().V(), g.graph().E()), (9, 8));
+        assert_eq!(g.id("standalone"), Some(8));
+        assert_eq!(g.label(0), "app");
+        assert_eq!(g.graph().edge_weight(g.id("serde").unwrap(), 3), Some(1.5));
+
+        let (len, path) = g.shortest_path("app", "unicode-ident").unwrap();
+        assert_eq!(len, 9.0);
+        assert_eq!(
+            path,
+            vec![
+                "app",
+                "serde",
+                "serde_derive",
+                "syn",
+                "proc-macro2",
+                "unicode-ident"
+            ]
+        );
+        assert_eq!(g.shortest_path("log", "app"), None);
+        assert_eq!(g.shortest_path("app", "missing"), None);
+
+        let order = g.topological_order().unwrap();
+        let pos = |name: &str| order.iter().position(|l| *l == name).unwrap();
+        assert!(pos("app") < pos("serde") && pos("syn") < pos("quote"));
+        assert!(pos("quote") < pos("proc-macro2"));
+        assert_eq!(g.stronge_connected_components().len(), 9);
+    }
+
+    #[test]
+    fn labels_in_mst_output() {
+        let mut g: LabeledGraph<UndiGraph<i32>> = LabeledGraph::new(UndiGraph::new(0));
+        assert!(g.add_edge_by_label("a", "b", 4));
+        assert!(g.add_edge_by_label("b", "c", 1));
+        assert!(g.add_edge_by_label("a", "c", 2));
+        assert_eq!(g.add_vertex("b".to_string()), 1);
+
+        let mut mst: Vec<(String, String, i32)> = g
+            .kerskal_msf()
+            .into_iter()
+            .map(|(u, v, w)| (u.clone().min(v.clone()), u.clone().max(v.clone()), w))
+            .collect();
+        mst.sort();
+        assert_eq!(
+            mst,
+            vec![
+                ("a".to_string(), "c".to_string(), 2),
+                ("b".to_string(), "c".to_string(), 1)
+            ]
+        );
+
+        let err = parse_labeled_graph(UndiGraph::<i32>::new(0), "a b x\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(parse_labeled_graph(UndiGraph::<f32>::new(0), "a b NaN\n".as_bytes()).is_err());
+        let err = parse_labeled_graph(DictetedGraph::<i32>::new(0), "a b\na a 1\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 2: edge `a a 1` rejected");
+    }
+
+    #[test]
+    fn rejected_edges_add_no_vertices() {
+        let mut g: LabeledGraph<DictetedGraph<i32>> = LabeledGraph::new(DictetedGraph::new(0));
+        assert!(!g.add_edge_by_label("a", "a", 1));
+        assert_eq!((g.graph().V(), g.labels().len()), (0, 0));
+        assert!(g.add_edge_by_label("a", "b", 1));
+        assert!(!g.add_edge_by_label("b", "b", 1));
+        assert_eq!((g.graph().V(), g.graph().E()), (2, 1));
+
+        let mut g: LabeledGraph<UndiGraph<f64>> = LabeledGraph::new(UndiGraph::new(0));
+        assert!(!g.add_edge_by_label("a", "b", f64::NAN));
+        assert_eq!((g.graph().V(), g.labels().len()), (0, 0));
+        // an undirected self-loop is fine
+        assert!(g.add_edge_by_label("a", "a", 1.0));
+        assert_eq!(g.graph().V(), 1);
+    }
+}
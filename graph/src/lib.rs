@@ -1,10 +1,29 @@
-// mod adjacency_list;
-// use adjacency_list::*;
 // #![feature(total_cmp)]
 
+pub mod adjacency_list;
+pub mod approx;
+pub mod assignment;
+pub mod csr;
+pub mod cut;
+pub mod cycle;
+pub mod dfs;
 pub mod directed_graph;
+pub mod dominator;
+pub mod flow;
+pub mod labeled;
+pub mod matching;
 pub mod matrix_graph;
+pub mod metrics;
+pub mod pagerank;
+pub mod parallel;
+pub mod search;
+pub mod subgraph;
+#[cfg(test)]
+mod test_util;
+pub mod traits;
+pub mod two_sat;
 pub mod undirected_graph;
+pub mod weight;
 // pub use directed_graph::*;
 // pub use undirected_graph::*;
 
@@ -0,0 +1,688 @@
+// Maximum-weight matching in general graphs: Edmonds' blossom algorithm in
+// Galil's O(n^3) primal-dual form, after Van Rantwijk's implementation.
+//
+// Weights are taken as f64 (as in `assignment`). Every vertex v carries a
+// dual u[v] and every top-level blossom b a dual z[b] >= 0, so that the
+// slack u[i] + u[j] - 2w of an edge (plus twice the z of the blossoms
+// holding both ends) stays non-negative and is zero on matched edges.
+//
+// Edge k joins edges[k].0 and edges[k].1; its endpoints are numbered 2k and
+// 2k + 1, so that p ^ 1 is the other end of endpoint p. Vertices are
+// 0..n and blossoms n..2n.
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+const NONE: usize = usize::MAX;
+
+// Labels of the alternating forest: free, outer (S) and inner (T); BREADCRUMB
+// marks the blossoms scan_blossom has passed.
+const FREE: u8 = 0;
+const OUTER: u8 = 1;
+const INNER: u8 = 2;
+const BREADCRUMB: u8 = 5;
+
+struct Blossom<'a> {
+    n: usize,
+    edges: &'a [(usize, usize, f64)],
+    endpoint: Vec<usize>,
+    // endpoints of the edges at each vertex, pointing away from it
+    neighbend: Vec<Vec<usize>>,
+    // the remote endpoint of the matched edge of each vertex
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    // the endpoint through which a labelled vertex or blossom was reached
+    labelend: Vec<usize>,
+    // the top-level blossom of each vertex
+    inblossom: Vec<usize>,
+    parent: Vec<usize>,
+    // sub-blossoms in cyclic order from the base, and the endpoints of the
+    // edges joining consecutive ones
+    childs: Vec<Vec<usize>>,
+    endps: Vec<Vec<usize>>,
+    base: Vec<usize>,
+    // least-slack edge to a different outer blossom, per vertex or blossom
+    bestedge: Vec<usize>,
+    bestedges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<f64>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<'a> Blossom<'a> {
+    fn new(n: usize, edges: &'a [(usize, usize, f64)]) -> Self {
+        let max_weight = edges.iter().fold(0.0, |m: f64, e| m.max(e.2));
+        let mut neighbend = vec![vec![]; n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbend[i].push(2 * k + 1);
+            neighbend[j].push(2 * k);
+        }
+        Blossom {
+            n,
+            edges,
+            endpoint: (0..2 * edges.len())
+                .map(|p| {
+                    if p % 2 == 0 {
+                        edges[p / 2].0
+                    } else {
+                        edges[p / 2].1
+                    }
+                })
+                .collect(),
+            neighbend,
+            mate: vec![NONE; n],
+            label: vec![FREE; 2 * n],
+            labelend: vec![NONE; 2 * n],
+            inblossom: (0..n).collect(),
+            parent: vec![NONE; 2 * n],
+            childs: vec![vec![]; 2 * n],
+            endps: vec![vec![]; 2 * n],
+            base: (0..n).chain(std::iter::repeat_n(NONE, n)).collect(),
+            bestedge: vec![NONE; 2 * n],
+            bestedges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual: (0..2 * n)
+                .map(|v| if v < n { max_weight } else { 0.0 })
+                .collect(),
+            allowed: vec![false; edges.len()],
+            queue: vec![],
+        }
+    }
+
+    fn slack(&self, k: usize) -> f64 {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - 2.0 * w
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        fn collect(s: &Blossom, b: usize, out: &mut Vec<usize>) {
+            if b < s.n {
+                out.push(b);
+            } else {
+                for &t in &s.childs[b] {
+                    collect(s, t, out);
+                }
+            }
+        }
+        let mut out = vec![];
+        collect(self, b, &mut out);
+        out
+    }
+
+    // Index into a blossom's cycle, counting from the end when negative.
+    fn at(cycle: &[usize], j: isize) -> usize {
+        cycle[j.rem_euclid(cycle.len() as isize) as usize]
+    }
+
+    // Label the top-level blossom of w, reached through endpoint p; an
+    // inner blossom's mate becomes outer in turn.
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.inblossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.labelend[w] = p;
+        self.labelend[b] = p;
+        self.bestedge[w] = NONE;
+        self.bestedge[b] = NONE;
+        if t == OUTER {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let m = self.mate[self.base[b]];
+            self.assign_label(self.endpoint[m], OUTER, m ^ 1);
+        }
+    }
+
+    // Trace back from the outer vertices v and w to their roots; returns
+    // the base of the new blossom if the paths meet, NONE if they reach two
+    // different roots (an augmenting path).
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = vec![];
+        let mut base = NONE;
+        while v != NONE {
+            let mut b = self.inblossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = BREADCRUMB;
+            if self.labelend[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.labelend[b]];
+                b = self.inblossom[v];
+                v = self.endpoint[self.labelend[b]];
+            }
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = OUTER;
+        }
+        base
+    }
+
+    // Shrink the odd cycle closed by edge k into a new outer blossom.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.inblossom[base];
+        let mut bv = self.inblossom[v];
+        let mut bw = self.inblossom[w];
+        let b = self.unused.pop().unwrap();
+        self.base[b] = base;
+        self.parent[b] = NONE;
+        self.parent[bb] = b;
+        let mut path = vec![];
+        let mut endps = vec![];
+        while bv != bb {
+            self.parent[bv] = b;
+            path.push(bv);
+            endps.push(self.labelend[bv]);
+            v = self.endpoint[self.labelend[bv]];
+            bv = self.inblossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = b;
+            path.push(bw);
+            endps.push(self.labelend[bw] ^ 1);
+            w = self.endpoint[self.labelend[bw]];
+            bw = self.inblossom[w];
+        }
+        self.label[b] = OUTER;
+        self.labelend[b] = self.labelend[bb];
+        self.dual[b] = 0.0;
+        self.childs[b] = path.clone();
+        self.endps[b] = endps;
+        for v in self.leaves(b) {
+            if self.label[self.inblossom[v]] == INNER {
+                self.queue.push(v);
+            }
+            self.inblossom[v] = b;
+        }
+
+        let mut bestedgeto = vec![NONE; 2 * self.n];
+        for &bv in &path {
+            let lists = match self.bestedges[bv].take() {
+                Some(list) => vec![list],
+                None => self
+                    .leaves(bv)
+                    .into_iter()
+                    .map(|v| self.neighbend[v].iter().map(|p| p / 2).collect())
+                    .collect(),
+            };
+            for k in lists.into_iter().flatten() {
+                let (i, j, _) = self.edges[k];
+                let j = if self.inblossom[j] == b { i } else { j };
+                let bj = self.inblossom[j];
+                if bj != b
+                    && self.label[bj] == OUTER
+                    && (bestedgeto[bj] == NONE || self.slack(k) < self.slack(bestedgeto[bj]))
+                {
+                    bestedgeto[bj] = k;
+                }
+            }
+            self.bestedge[bv] = NONE;
+        }
+        let list: Vec<usize> = bestedgeto.into_iter().filter(|&k| k != NONE).collect();
+        self.bestedge[b] = NONE;
+        for &k in &list {
+            if self.bestedge[b] == NONE || self.slack(k) < self.slack(self.bestedge[b]) {
+                self.bestedge[b] = k;
+            }
+        }
+        self.bestedges[b] = Some(list);
+    }
+
+    // Undo blossom b; at the end of a stage its zero-dual sub-blossoms go
+    // too, and an inner blossom relabels the path through it.
+    fn expand_blossom(&mut self, b: usize, endstage: bool) {
+        for s in self.childs[b].clone() {
+            self.parent[s] = NONE;
+            if s < self.n {
+                self.inblossom[s] = s;
+            } else if endstage && self.dual[s] == 0.0 {
+                self.expand_blossom(s, endstage);
+            } else {
+                for v in self.leaves(s) {
+                    self.inblossom[v] = s;
+                }
+            }
+        }
+
+        if !endstage && self.label[b] == INNER {
+            let childs = self.childs[b].clone();
+            let endps = self.endps[b].clone();
+            let entrychild = self.inblossom[self.endpoint[self.labelend[b] ^ 1]];
+            let mut j = childs.iter().position(|&c| c == entrychild).unwrap() as isize;
+            // go around the cycle the even way to the base
+            let (jstep, endptrick) = if j & 1 == 1 {
+                j -= childs.len() as isize;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.labelend[b];
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = FREE;
+                let q = Self::at(&endps, j - endptrick as isize);
+                self.label[self.endpoint[q ^ endptrick ^ 1]] = FREE;
+                self.assign_label(self.endpoint[p ^ 1], INNER, p);
+                self.allowed[q / 2] = true;
+                j += jstep;
+                p = Self::at(&endps, j - endptrick as isize) ^ endptrick;
+                self.allowed[p / 2] = true;
+                j += jstep;
+            }
+            let bv = Self::at(&childs, j);
+            self.label[self.endpoint[p ^ 1]] = INNER;
+            self.label[bv] = INNER;
+            self.labelend[self.endpoint[p ^ 1]] = p;
+            self.labelend[bv] = p;
+            self.bestedge[bv] = NONE;
+            j += jstep;
+            // the sub-blossoms off the path may have been reached from outside
+            while Self::at(&childs, j) != entrychild {
+                let bv = Self::at(&childs, j);
+                j += jstep;
+                if self.label[bv] == OUTER {
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != FREE) {
+                    self.label[v] = FREE;
+                    self.label[self.endpoint[self.mate[self.base[bv]]]] = FREE;
+                    self.assign_label(v, INNER, self.labelend[v]);
+                }
+            }
+        }
+
+        self.label[b] = FREE;
+        self.labelend[b] = NONE;
+        self.childs[b].clear();
+        self.endps[b].clear();
+        self.base[b] = NONE;
+        self.bestedges[b] = None;
+        self.bestedge[b] = NONE;
+        self.unused.push(b);
+    }
+
+    // Flip the matched edges along the even path from vertex v to the base
+    // of blossom b, which becomes v.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != b {
+            t = self.parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+        let i = self.childs[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (jstep, endptrick) = if i & 1 == 1 {
+            j -= self.childs[b].len() as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += jstep;
+            let t = Self::at(&self.childs[b], j);
+            let p = Self::at(&self.endps[b], j - endptrick as isize) ^ endptrick;
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += jstep;
+            let t = Self::at(&self.childs[b], j);
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.childs[b].rotate_left(i);
+        self.endps[b].rotate_left(i);
+        self.base[b] = self.base[self.childs[b][0]];
+    }
+
+    // Flip the augmenting path through edge k, back to both roots.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.inblossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.labelend[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.labelend[bs]];
+                let bt = self.inblossom[t];
+                s = self.endpoint[self.labelend[bt]];
+                let j = self.endpoint[self.labelend[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.labelend[bt];
+                p = self.labelend[bt] ^ 1;
+            }
+        }
+    }
+
+    // Grow the forest from the free vertices until an augmenting path is
+    // found; false when the matching is already optimal.
+    fn stage(&mut self, max_cardinality: bool) -> bool {
+        let n = self.n;
+        self.label.iter_mut().for_each(|l| *l = FREE);
+        self.bestedge.iter_mut().for_each(|e| *e = NONE);
+        self.bestedges[n..].iter_mut().for_each(|l| *l = None);
+        self.allowed.iter_mut().for_each(|a| *a = false);
+        self.queue.clear();
+        for v in 0..n {
+            if self.mate[v] == NONE && self.label[self.inblossom[v]] == FREE {
+                self.assign_label(v, OUTER, NONE);
+            }
+        }
+
+        loop {
+            while let Some(v) = self.queue.pop() {
+                for p in self.neighbend[v].clone() {
+                    let k = p / 2;
+                    let w = self.endpoint[p];
+                    if self.inblossom[v] == self.inblossom[w] {
+                        continue;
+                    }
+                    let mut kslack = 0.0;
+                    if !self.allowed[k] {
+                        kslack = self.slack(k);
+                        self.allowed[k] = kslack <= 0.0;
+                    }
+                    let bw = self.inblossom[w];
+                    if self.allowed[k] {
+                        if self.label[bw] == FREE {
+                            self.assign_label(w, INNER, p ^ 1);
+                        } else if self.label[bw] == OUTER {
+                            let base = self.scan_blossom(v, w);
+                            if base != NONE {
+                                self.add_blossom(base, k);
+                            } else {
+                                self.augment_matching(k);
+                                return true;
+                            }
+                        } else if self.label[w] == FREE {
+                            // w is inside an inner blossom but not yet reached
+                            self.label[w] = INNER;
+                            self.labelend[w] = p ^ 1;
+                        }
+                    } else if self.label[bw] == OUTER {
+                        let b = self.inblossom[v];
+                        if self.bestedge[b] == NONE || kslack < self.slack(self.bestedge[b]) {
+                            self.bestedge[b] = k;
+                        }
+                    } else if self.label[w] == FREE
+                        && (self.bestedge[w] == NONE || kslack < self.slack(self.bestedge[w]))
+                    {
+                        self.bestedge[w] = k;
+                    }
+                }
+            }
+
+            // No tight edge left: move the duals by the least delta that
+            // makes one, or frees a vertex or an inner blossom.
+            let min_vertex_dual = self.dual[..n].iter().cloned().fold(f64::INFINITY, f64::min);
+            let mut best: Option<(f64, u8, usize)> = None;
+            let mut consider = |delta: f64, kind: u8, at: usize| {
+                if best.is_none_or(|(d, _, _)| delta < d) {
+                    best = Some((delta, kind, at));
+                }
+            };
+            if !max_cardinality {
+                consider(min_vertex_dual, 1, NONE);
+            }
+            for v in 0..n {
+                if self.label[self.inblossom[v]] == FREE && self.bestedge[v] != NONE {
+                    consider(self.slack(self.bestedge[v]), 2, self.bestedge[v]);
+                }
+            }
+            for b in 0..2 * n {
+                if self.parent[b] == NONE && self.label[b] == OUTER && self.bestedge[b] != NONE {
+                    consider(self.slack(self.bestedge[b]) / 2.0, 3, self.bestedge[b]);
+                }
+            }
+            for b in n..2 * n {
+                if self.base[b] != NONE && self.parent[b] == NONE && self.label[b] == INNER {
+                    consider(self.dual[b], 4, b);
+                }
+            }
+            // with max_cardinality, nothing left to grow means it is done
+            let (delta, kind, at) = best.unwrap_or((min_vertex_dual.max(0.0), 1, NONE));
+
+            for v in 0..n {
+                match self.label[self.inblossom[v]] {
+                    OUTER => self.dual[v] -= delta,
+                    INNER => self.dual[v] += delta,
+                    _ => {}
+                }
+            }
+            for b in n..2 * n {
+                if self.base[b] != NONE && self.parent[b] == NONE {
+                    match self.label[b] {
+                        OUTER => self.dual[b] += delta,
+                        INNER => self.dual[b] -= delta,
+                        _ => {}
+                    }
+                }
+            }
+
+            match kind {
+                1 => return false,
+                2 => {
+                    self.allowed[at] = true;
+                    let (i, j, _) = self.edges[at];
+                    let i = if self.label[self.inblossom[i]] == FREE {
+                        j
+                    } else {
+                        i
+                    };
+                    self.queue.push(i);
+                }
+                3 => {
+                    self.allowed[at] = true;
+                    self.queue.push(self.edges[at].0);
+                }
+                _ => self.expand_blossom(at, false),
+            }
+        }
+    }
+
+    fn solve(mut self, max_cardinality: bool) -> Vec<Option<usize>> {
+        for _ in 0..self.n {
+            if !self.stage(max_cardinality) {
+                break;
+            }
+            for b in self.n..2 * self.n {
+                if self.parent[b] == NONE
+                    && self.base[b] != NONE
+                    && self.label[b] == OUTER
+                    && self.dual[b] == 0.0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+        self.mate
+            .iter()
+            .map(|&p| if p == NONE { None } else { Some(p / 2) })
+            .collect()
+    }
+}
+
+// The matched edge of every vertex, as an index into `edges`, in a matching
+// of greatest total weight; with `max_cardinality`, of greatest weight
+// among those with the most edges.
+fn solve(n: usize, edges: &[(usize, usize, f64)], max_cardinality: bool) -> Vec<Option<usize>> {
+    Blossom::new(n, edges).solve(max_cardinality)
+}
+
+// A perfect matching of `vertices`, an even number of them, of least total
+// distance d.
+pub(crate) fn min_weight_perfect_matching<W: Weight>(
+    d: &[Vec<W>],
+    vertices: &[usize],
+) -> Vec<(usize, usize)> {
+    assert!(
+        vertices.len() % 2 == 0,
+        "a perfect matching needs an even number of vertices"
+    );
+    let k = vertices.len();
+    let mut edges = vec![];
+    for i in 0..k {
+        for j in i + 1..k {
+            edges.push((i, j, d[vertices[i]][vertices[j]].to_f64()));
+        }
+    }
+    // heaviest first turns into lightest first
+    let top = edges.iter().fold(0.0, |m: f64, e| m.max(e.2));
+    for e in edges.iter_mut() {
+        e.2 = top - e.2;
+    }
+    let mate = solve(k, &edges, true);
+    // each pair once, from its smaller end
+    (0..k)
+        .filter_map(|i| mate[i].map(|e| edges[e]).filter(|e| e.0 == i))
+        .map(|(u, v, _)| (vertices[u], vertices[v]))
+        .collect()
+}
+
+impl<W: Weight> UndiGraph<W> {
+    // A matching of greatest total weight, or with `max_cardinality` the
+    // heaviest among those with the most edges. Returns the total weight
+    // and the matched edges as (u, v, weight) with u < v.
+    pub fn max_weight_matching(&self, max_cardinality: bool) -> (W, Vec<(usize, usize, W)>) {
+        let edges: Vec<(usize, usize, W)> = self
+            .iter_edges()
+            .map(|e| {
+                let (u, v) = e.ends();
+                (u.min(v), u.max(v), e.weight())
+            })
+            .filter(|&(u, v, _)| u != v)
+            .collect();
+        let weights: Vec<(usize, usize, f64)> =
+            edges.iter().map(|&(u, v, w)| (u, v, w.to_f64())).collect();
+        let mate = solve(self.V(), &weights, max_cardinality);
+        let matched: Vec<(usize, usize, W)> = (0..self.V())
+            .filter_map(|v| mate[v].map(|e| edges[e]).filter(|e| e.0 == v))
+            .collect();
+        let total = matched
+            .iter()
+            .fold(W::zero(), |acc, e| acc.saturating_plus(e.2));
+        (total, matched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lcg, random_edges};
+
+    // (edges, weight) of the best matching, by trying every edge in or out
+    fn brute_force(edges: &[(usize, usize, i64)], max_cardinality: bool) -> (usize, i64) {
+        fn best(
+            edges: &[(usize, usize, i64)],
+            used: &mut Vec<bool>,
+            max_cardinality: bool,
+        ) -> (usize, i64) {
+            let (&(u, v, w), rest) = match edges.split_first() {
+                Some(split) => split,
+                None => return (0, 0),
+            };
+            let mut result = best(rest, used, max_cardinality);
+            if u != v && !used[u] && !used[v] {
+                used[u] = true;
+                used[v] = true;
+                let (c, t) = best(rest, used, max_cardinality);
+                used[u] = false;
+                used[v] = false;
+                let with = (c + 1, t + w);
+                let better = if max_cardinality {
+                    with > result
+                } else {
+                    with.1 > result.1
+                };
+                if better {
+                    result = with;
+                }
+            }
+            result
+        }
+        let n = edges.iter().map(|e| e.0.max(e.1) + 1).max().unwrap_or(0);
+        best(edges, &mut vec![false; n], max_cardinality)
+    }
+
+    // every perfect matching of 0..k, pairing off the lowest vertex first
+    fn cheapest_perfect(d: &[Vec<i64>], free: &[usize]) -> i64 {
+        match free.split_first() {
+            None => 0,
+            Some((&u, rest)) => (0..rest.len())
+                .map(|i| {
+                    let mut others = rest.to_vec();
+                    let v = others.remove(i);
+                    d[u][v] + cheapest_perfect(d, &others)
+                })
+                .min()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut next = lcg(11);
+        for round in 0..200 {
+            let n = 2 + round % 9;
+            let m = next() % (n * (n - 1) / 2 + 1);
+            let edges = random_edges(n, m, &mut next, |r| (r % 20) as i64 - 4);
+            let g = UndiGraph::build_graph(n, edges.clone());
+            for &max_cardinality in &[false, true] {
+                let (total, matched) = g.max_weight_matching(max_cardinality);
+                let mut seen = vec![false; n];
+                for &(u, v, w) in &matched {
+                    assert!(!std::mem::replace(&mut seen[u], true));
+                    assert!(!std::mem::replace(&mut seen[v], true));
+                    assert!(edges.contains(&(u, v, w)) || edges.contains(&(v, u, w)));
+                }
+                assert_eq!(total, matched.iter().map(|e| e.2).sum::<i64>());
+                let (count, weight) = brute_force(&edges, max_cardinality);
+                assert_eq!(total, weight, "{:?} {}", edges, max_cardinality);
+                if max_cardinality {
+                    assert_eq!(matched.len(), count);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_matching_of_least_weight() {
+        let mut next = lcg(5);
+        for round in 0..60 {
+            let k = 2 * (1 + round % 5);
+            // symmetric, from the upper triangle of random weights
+            let w: Vec<i64> = (0..k * k).map(|_| (next() % 50) as i64).collect();
+            let d: Vec<Vec<i64>> = (0..k)
+                .map(|u| (0..k).map(|v| w[u.min(v) * k + u.max(v)]).collect())
+                .collect();
+            let vertices: Vec<usize> = (0..k).collect();
+            let pairs = min_weight_perfect_matching(&d, &vertices);
+            let mut ends: Vec<usize> = pairs.iter().flat_map(|&(u, v)| vec![u, v]).collect();
+            ends.sort();
+            assert_eq!(ends, vertices);
+            let cost: i64 = pairs.iter().map(|&(u, v)| d[u][v]).sum();
+            assert_eq!(cost, cheapest_perfect(&d, &vertices));
+        }
+    }
+}
@@ -1,117 +1,368 @@
-pub mod helper {
-
-    pub fn print_matrix(mat: &Vec<Vec<f32>>) {
-        for line in mat {
-            print!("[ ");
-            for i in line {
-                if (f32::MAX - *i).abs() <= f32::EPSILON {
-                    print! {"INF   "};
-                } else {
-                    print!("{: <5.1} ", i);
-                }
-            }
-            println!("]");
-        }
-        println!();
-    }
-
-    pub fn parse_graph_to_matrix() -> Vec<Vec<f32>> {
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-
-        // println!("parse_graph_from_stdio:  {}", input);
-        let v = input.trim().parse::<usize>().unwrap();
-        let mut mat = vec![vec![f32::MAX; v]; v];
-        for i in 0..v {
-            mat[i][i] = 0.0;
-        }
-
-        input.clear();
-        std::io::stdin().read_line(&mut input).unwrap();
-        let e = input.trim().parse::<usize>().unwrap();
-
-        for _ in 0..e {
-            input.clear();
-            std::io::stdin().read_line(&mut input).unwrap();
-            // println!("{:?}", input.split_ascii_whitespace());
-            // let nums: Vec<&str> = input.trim().split(' ').collect();
-            let nums: Vec<&str> = input.split_ascii_whitespace().collect();
-            // println!("{:?}", nums);
-            // println!("{} {}", nums[0], nums[1]);
-            let i = nums[0].trim().parse::<usize>().unwrap();
-            let j = nums[1].trim().parse::<usize>().unwrap();
-            let weight = nums[2].trim().parse::<f32>().unwrap_or(1.0);
-
-            mat[i][j] = weight;
-        }
-
-        mat
-    }
-}
-
-use helper::*;
-
-pub fn extend_shortest_paths(l: &Vec<Vec<f32>>, w: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
-    let n = l.len();
-    let mut nextl = vec![vec![0.0; n]; n];
-
-    for i in 0..n {
-        for j in 0..n {
-            if i != j {
-                nextl[i][j] = f32::MAX;
-            }
-            for k in 0..n {
-                nextl[i][j] = f32::min(nextl[i][j], l[i][k] + w[k][j]);
-            }
-        }
-    }
-
-    nextl
-}
-
-pub fn show_all_pairs_shortest_paths(w: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
-    let n = w.len();
-    let mut last_l = w.clone();
-    print_matrix(&last_l);
-
-    for _ in 2..n {
-        last_l = extend_shortest_paths(&last_l, w);
-        print_matrix(&last_l);
-    }
-    last_l
-}
-
-pub fn faster_all_shortest_paths(w: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
-    let n = w.len();
-    let mut l2m = w.clone();
-    print_matrix(&l2m);
-
-    let mut m = 1;
-    while m < n - 1 {
-        l2m = extend_shortest_paths(&l2m, &l2m);
-        print_matrix(&l2m);
-        m *= 2;
-    }
-
-    l2m
-}
-
-pub fn floyd_warshall(w: &Vec<Vec<f32>>) -> Vec<Vec<f32>> {
-    let n = w.len();
-    let mut last_d = w.clone();
-    print_matrix(&last_d);
-
-    for k in 0..n {
-        // let mut new_d = vec![vec![0.0; n]; n];
-        for i in 0..n {
-            for j in 0..n {
-                last_d[i][j] = f32::min(last_d[i][j], last_d[i][k] + last_d[k][j]);
-            }
-        }
-
-        // last_d = new_d;
-        print_matrix(&last_d);
-    }
-
-    last_d
-}
+pub mod helper {
+    use crate::weight::Weight;
+
+    pub fn print_matrix<W: Weight>(mat: &[Vec<W>]) {
+        for line in mat {
+            print!("[ ");
+            for i in line {
+                if i.is_infinite() {
+                    print! {"INF   "};
+                } else {
+                    print!("{: <5.1} ", i);
+                }
+            }
+            println!("]");
+        }
+        println!();
+    }
+
+    // NaN weights are rejected here, before any algorithm sees them.
+    pub fn check_matrix<W: Weight>(mat: &[Vec<W>]) {
+        for (i, line) in mat.iter().enumerate() {
+            assert_eq!(line.len(), mat.len(), "row {} is not {} wide", i, mat.len());
+            if let Some(j) = line.iter().position(|w| !w.is_valid()) {
+                panic!("invalid weight {:?} at ({}, {})", line[j], i, j);
+            }
+        }
+    }
+
+    pub fn parse_graph_to_matrix<W>() -> Vec<Vec<W>>
+    where
+        W: Weight + std::str::FromStr,
+    {
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+
+        // println!("parse_graph_from_stdio:  {}", input);
+        let v = input.trim().parse::<usize>().unwrap();
+        let mut mat = vec![vec![W::infinity(); v]; v];
+        for (i, row) in mat.iter_mut().enumerate() {
+            row[i] = W::zero();
+        }
+
+        input.clear();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let e = input.trim().parse::<usize>().unwrap();
+
+        for _ in 0..e {
+            input.clear();
+            std::io::stdin().read_line(&mut input).unwrap();
+            // println!("{:?}", input.split_ascii_whitespace());
+            // let nums: Vec<&str> = input.trim().split(' ').collect();
+            let nums: Vec<&str> = input.split_ascii_whitespace().collect();
+            // println!("{:?}", nums);
+            // println!("{} {}", nums[0], nums[1]);
+            let i = nums[0].trim().parse::<usize>().unwrap();
+            let j = nums[1].trim().parse::<usize>().unwrap();
+            let weight = nums[2].trim().parse::<W>().unwrap_or_else(|_| W::one());
+
+            mat[i][j] = weight;
+        }
+
+        check_matrix(&mat);
+        mat
+    }
+}
+
+use crate::traits::Graph;
+use crate::weight::Weight;
+use helper::*;
+
+// A dense matrix is a directed graph whose infinite entries are missing
+// edges; the diagonal is ignored.
+impl<W: Weight> Graph for [Vec<W>] {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        (0..self.len())
+            .map(|i| {
+                self[i]
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, w)| i != j && !w.is_infinite())
+                    .count()
+            })
+            .sum()
+    }
+
+    fn is_directed(&self) -> bool {
+        true
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, W),
+    {
+        for (u, &w) in self[v].iter().enumerate() {
+            if u != v && !w.is_infinite() {
+                f(u, w);
+            }
+        }
+    }
+
+    fn edge_weight(&self, from: usize, to: usize) -> Option<W> {
+        Some(self[from][to]).filter(|w| from != to && !w.is_infinite())
+    }
+}
+
+pub fn extend_shortest_paths<W: Weight>(l: &[Vec<W>], w: &[Vec<W>]) -> Vec<Vec<W>> {
+    check_matrix(l);
+    check_matrix(w);
+    let n = l.len();
+    let mut nextl = vec![vec![W::zero(); n]; n];
+
+    for i in 0..n {
+        for j in 0..n {
+            if i != j {
+                nextl[i][j] = W::infinity();
+            }
+            for k in 0..n {
+                nextl[i][j] = nextl[i][j].min_weight(l[i][k].saturating_plus(w[k][j]));
+            }
+        }
+    }
+
+    nextl
+}
+
+pub fn show_all_pairs_shortest_paths<W: Weight>(w: &[Vec<W>]) -> Vec<Vec<W>> {
+    let n = w.len();
+    let mut last_l = w.to_vec();
+    print_matrix(&last_l);
+
+    for _ in 2..n {
+        last_l = extend_shortest_paths(&last_l, w);
+        print_matrix(&last_l);
+    }
+    last_l
+}
+
+pub fn faster_all_shortest_paths<W: Weight>(w: &[Vec<W>]) -> Vec<Vec<W>> {
+    let n = w.len();
+    let mut l2m = w.to_vec();
+    print_matrix(&l2m);
+
+    let mut m = 1;
+    while m < n - 1 {
+        l2m = extend_shortest_paths(&l2m, &l2m);
+        print_matrix(&l2m);
+        m *= 2;
+    }
+
+    l2m
+}
+
+pub fn floyd_warshall<W: Weight>(w: &[Vec<W>]) -> Vec<Vec<W>> {
+    check_matrix(w);
+    let n = w.len();
+    let mut last_d = w.to_vec();
+    print_matrix(&last_d);
+
+    for k in 0..n {
+        // let mut new_d = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                last_d[i][j] = last_d[i][j].min_weight(last_d[i][k].saturating_plus(last_d[k][j]));
+            }
+        }
+
+        // last_d = new_d;
+        print_matrix(&last_d);
+    }
+
+    last_d
+}
+
+// Cache-blocked, multithreaded variants of the all-pairs algorithms above.
+// Rows are handed out to `threads` workers and every worker walks its rows
+// tile by tile, so the pivot rows it reads stay hot in cache.
+
+pub const DEFAULT_BLOCK_SIZE: usize = 64;
+
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn rows_per_thread(n: usize, threads: usize) -> usize {
+    n.div_ceil(threads.max(1)).max(1)
+}
+
+pub fn blocked_extend_shortest_paths<W: Weight>(
+    l: &[Vec<W>],
+    w: &[Vec<W>],
+    block: usize,
+    threads: usize,
+) -> Vec<Vec<W>> {
+    check_matrix(l);
+    check_matrix(w);
+    let n = l.len();
+    let block = block.max(1);
+    let mut nextl = vec![vec![W::infinity(); n]; n];
+    for (i, row) in nextl.iter_mut().enumerate() {
+        row[i] = W::zero();
+    }
+
+    let chunk = rows_per_thread(n, threads);
+    std::thread::scope(|s| {
+        for (chunk_idx, rows) in nextl.chunks_mut(chunk).enumerate() {
+            let first = chunk_idx * chunk;
+            s.spawn(move || {
+                for k0 in (0..n).step_by(block) {
+                    let k1 = (k0 + block).min(n);
+                    for j0 in (0..n).step_by(block) {
+                        let j1 = (j0 + block).min(n);
+                        for (r, row) in rows.iter_mut().enumerate() {
+                            let li = &l[first + r];
+                            for k in k0..k1 {
+                                let lik = li[k];
+                                let wk = &w[k];
+                                for j in j0..j1 {
+                                    row[j] = row[j].min_weight(lik.saturating_plus(wk[j]));
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    nextl
+}
+
+pub fn par_faster_all_shortest_paths<W: Weight>(w: &[Vec<W>]) -> Vec<Vec<W>> {
+    let n = w.len();
+    let mut l2m = w.to_vec();
+
+    let mut m = 1;
+    while m + 1 < n {
+        l2m = blocked_extend_shortest_paths(&l2m, &l2m, DEFAULT_BLOCK_SIZE, default_threads());
+        m *= 2;
+    }
+
+    l2m
+}
+
+pub fn blocked_floyd_warshall<W: Weight>(
+    w: &[Vec<W>],
+    block: usize,
+    threads: usize,
+) -> Vec<Vec<W>> {
+    check_matrix(w);
+    let n = w.len();
+    let block = block.max(1);
+    let mut d = w.to_vec();
+
+    for k0 in (0..n).step_by(block) {
+        let k1 = (k0 + block).min(n);
+
+        // The pivot rows only depend on themselves for k in this block,
+        // so they are finished first with the plain triple loop.
+        for k in k0..k1 {
+            let (head, tail) = d.split_at_mut(k);
+            let (dk, tail) = tail.split_first_mut().unwrap();
+            for i in k0..k1 {
+                let di = match i.cmp(&k) {
+                    std::cmp::Ordering::Less => &mut head[i],
+                    std::cmp::Ordering::Greater => &mut tail[i - k - 1],
+                    std::cmp::Ordering::Equal => continue,
+                };
+                let dik = di[k];
+                for j in 0..n {
+                    di[j] = di[j].min_weight(dik.saturating_plus(dk[j]));
+                }
+            }
+        }
+
+        // Every other row reads only the finished pivot rows. The pivot column
+        // tile goes first so d[i][k] is final before the remaining tiles use it.
+        let (above, rest) = d.split_at_mut(k0);
+        let (pivot, below) = rest.split_at_mut(k1 - k0);
+        let pivot: &[Vec<W>] = pivot;
+        // one split of the rows on both sides of the pivot, so at most
+        // `threads` workers
+        let mut others: Vec<&mut Vec<W>> = above.iter_mut().chain(below.iter_mut()).collect();
+        let chunk = rows_per_thread(others.len(), threads);
+        let tiles: Vec<(usize, usize)> = std::iter::once((k0, k1))
+            .chain(
+                (0..n)
+                    .step_by(block)
+                    .filter(|&j0| j0 != k0)
+                    .map(|j0| (j0, (j0 + block).min(n))),
+            )
+            .collect();
+        let tiles = &tiles;
+
+        std::thread::scope(|s| {
+            for rows in others.chunks_mut(chunk) {
+                s.spawn(move || {
+                    for &(j0, j1) in tiles {
+                        for di in rows.iter_mut() {
+                            for (dk, k) in pivot.iter().zip(k0..k1) {
+                                let dik = di[k];
+                                for j in j0..j1 {
+                                    di[j] = di[j].min_weight(dik.saturating_plus(dk[j]));
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    d
+}
+
+pub fn par_floyd_warshall<W: Weight>(w: &[Vec<W>]) -> Vec<Vec<W>> {
+    blocked_floyd_warshall(w, DEFAULT_BLOCK_SIZE, default_threads())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    fn random_matrix(n: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut next = lcg(seed);
+        let mut mat = vec![vec![f32::INFINITY; n]; n];
+        for (i, row) in mat.iter_mut().enumerate() {
+            for (j, w) in row.iter_mut().enumerate() {
+                if i == j {
+                    *w = 0.0;
+                } else if next() % 4 == 0 {
+                    *w = (next() % 20) as f32;
+                }
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn blocked_floyd_warshall_matches_plain() {
+        for &(n, block, threads) in &[(1, 4, 2), (13, 4, 3), (40, 7, 4), (33, 64, 2)] {
+            let w = random_matrix(n, n as u64);
+            assert_eq!(
+                blocked_floyd_warshall(&w, block, threads),
+                floyd_warshall(&w)
+            );
+        }
+    }
+
+    #[test]
+    fn blocked_extend_matches_plain() {
+        for &(n, block, threads) in &[(1, 4, 2), (13, 4, 3), (40, 7, 4)] {
+            let w = random_matrix(n, 7 * n as u64);
+            let l = extend_shortest_paths(&w, &w);
+            assert_eq!(blocked_extend_shortest_paths(&w, &w, block, threads), l);
+            assert_eq!(
+                blocked_extend_shortest_paths(&l, &w, block, threads),
+                extend_shortest_paths(&l, &w)
+            );
+        }
+    }
+}
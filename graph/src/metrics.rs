@@ -0,0 +1,455 @@
+// Structural metrics and centralities for any `Graph`, directed or not.
+//
+// The distance-based ones (eccentricity and what derives from it,
+// closeness) take a distance matrix, so they work on hop counts from BFS,
+// weighted distances from Dijkstra, or the output of the all-pairs
+// algorithms in `matrix_graph` alike. An infinite entry is unreachable.
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+use crate::directed_graph::DictetedGraph;
+use crate::search;
+use crate::traits::Graph;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::{OrdWeight, Weight};
+
+// dist[u][v] in edges, by a BFS from every vertex, counted in the weight
+// type so that it sits alongside `weighted_distances`; infinite if
+// unreachable. Panics if a hop count doesn't fit the weight type (for an
+// integer, when it would reach the `MAX` sentinel).
+pub fn hop_distances<G: Graph + ?Sized>(g: &G) -> Vec<Vec<G::Weight>> {
+    let n = g.vertex_count();
+    // as_weight[k] is k hops in the weight type
+    let mut as_weight = vec![G::Weight::zero()];
+    (0..n)
+        .map(|s| {
+            let mut hops = vec![0; n];
+            let mut seen = vec![false; n];
+            let mut que = VecDeque::new();
+            seen[s] = true;
+            que.push_back(s);
+            while let Some(v) = que.pop_front() {
+                g.for_each_neighbor(v, |w, _| {
+                    if !std::mem::replace(&mut seen[w], true) {
+                        hops[w] = hops[v] + 1;
+                        que.push_back(w);
+                    }
+                });
+            }
+            (0..n)
+                .map(|v| {
+                    if !seen[v] {
+                        return G::Weight::infinity();
+                    }
+                    while as_weight.len() <= hops[v] {
+                        let next = as_weight[as_weight.len() - 1]
+                            .checked_plus(G::Weight::one())
+                            .expect("hop count doesn't fit in the weight type");
+                        as_weight.push(next);
+                    }
+                    as_weight[hops[v]]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// dist[u][v] by a Dijkstra from every vertex; weights must be non-negative.
+pub fn weighted_distances<G: Graph + ?Sized>(g: &G) -> Vec<Vec<G::Weight>> {
+    (0..g.vertex_count())
+        .map(|s| search::dijkstra(g, s).0)
+        .collect()
+}
+
+// res[k] is the number of vertices with k out-edges (k edges when
+// undirected); parallel edges count separately.
+pub fn degree_distribution<G: Graph + ?Sized>(g: &G) -> Vec<usize> {
+    let mut res = vec![];
+    for v in 0..g.vertex_count() {
+        let mut degree = 0;
+        g.for_each_neighbor(v, |_, _| degree += 1);
+        if res.len() <= degree {
+            res.resize(degree + 1, 0);
+        }
+        res[degree] += 1;
+    }
+    res
+}
+
+// The greatest distance out of every vertex; infinite if some vertex is
+// unreachable from it.
+pub fn eccentricities<W: Weight>(dist: &[Vec<W>]) -> Vec<W> {
+    dist.iter()
+        .map(|row| {
+            row.iter().fold(W::zero(), |m, &d| {
+                if d.cmp_weight(&m) == Ordering::Greater {
+                    d
+                } else {
+                    m
+                }
+            })
+        })
+        .collect()
+}
+
+fn extreme<W: Weight>(dist: &[Vec<W>], wanted: Ordering) -> (W, Vec<usize>) {
+    let ecc = eccentricities(dist);
+    let mut best: Option<W> = None;
+    let mut at = vec![];
+    for (v, &e) in ecc.iter().enumerate() {
+        match best.map(|b| e.cmp_weight(&b)) {
+            Some(Ordering::Equal) => at.push(v),
+            Some(o) if o != wanted => {}
+            _ => {
+                best = Some(e);
+                at = vec![v];
+            }
+        }
+    }
+    (best.unwrap_or_else(W::zero), at)
+}
+
+// Greatest eccentricity; infinite unless strongly connected.
+pub fn diameter<W: Weight>(dist: &[Vec<W>]) -> W {
+    extreme(dist, Ordering::Greater).0
+}
+
+// Least eccentricity.
+pub fn radius<W: Weight>(dist: &[Vec<W>]) -> W {
+    extreme(dist, Ordering::Less).0
+}
+
+// The vertices whose eccentricity is the radius.
+pub fn center<W: Weight>(dist: &[Vec<W>]) -> Vec<usize> {
+    extreme(dist, Ordering::Less).1
+}
+
+// The vertices whose eccentricity is the diameter.
+pub fn periphery<W: Weight>(dist: &[Vec<W>]) -> Vec<usize> {
+    extreme(dist, Ordering::Greater).1
+}
+
+// Closeness of every vertex over the distances out of it, scaled by the
+// share of vertices it reaches (Wasserman and Faust) so that a
+// disconnected graph still compares: (r / (n - 1)) * (r / total) for r
+// reachable others at `total` distance. 0 if it reaches none, and infinite
+// if it reaches some all at distance 0, over zero-weight edges.
+pub fn closeness<W: Weight>(dist: &[Vec<W>]) -> Vec<f64> {
+    let n = dist.len();
+    dist.iter()
+        .enumerate()
+        .map(|(v, row)| {
+            let reached = row
+                .iter()
+                .enumerate()
+                .filter(|&(u, d)| u != v && !d.is_infinite());
+            let (r, total) = reached.fold((0.0, 0.0), |(r, t), (_, d)| (r + 1.0, t + d.to_f64()));
+            if r == 0.0 {
+                0.0
+            } else if total == 0.0 {
+                f64::INFINITY
+            } else {
+                (r / (n - 1) as f64) * (r / total)
+            }
+        })
+        .collect()
+}
+
+// Brandes: the number of shortest paths through every vertex after one
+// search per source, summed back from the farthest vertices by
+// delta[v] = sum over successors w of sigma[v] / sigma[w] * (1 + delta[w]).
+fn accumulate(res: &mut [f64], s: usize, order: &[usize], preds: &[Vec<usize>], sigma: &[f64]) {
+    let mut delta = vec![0.0; res.len()];
+    for &w in order.iter().rev() {
+        for &v in preds[w].iter() {
+            delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+        }
+        if w != s {
+            res[w] += delta[w];
+        }
+    }
+}
+
+// Each pair counted once when undirected.
+fn halve_if_undirected<G: Graph + ?Sized>(g: &G, mut res: Vec<f64>) -> Vec<f64> {
+    if !g.is_directed() {
+        res.iter_mut().for_each(|b| *b /= 2.0);
+    }
+    res
+}
+
+// Betweenness of every vertex over shortest paths in edges: the sum over
+// pairs s, t of the share of shortest s-t paths that pass through it.
+// Unnormalized; O(VE).
+pub fn betweenness<G: Graph + ?Sized>(g: &G) -> Vec<f64> {
+    let n = g.vertex_count();
+    let mut res = vec![0.0; n];
+    for s in 0..n {
+        let mut order = vec![];
+        let mut preds = vec![vec![]; n];
+        let mut sigma = vec![0.0; n];
+        let mut dist = vec![usize::MAX; n];
+        let mut que = VecDeque::new();
+        sigma[s] = 1.0;
+        dist[s] = 0;
+        que.push_back(s);
+        while let Some(v) = que.pop_front() {
+            order.push(v);
+            g.for_each_neighbor(v, |w, _| {
+                if dist[w] == usize::MAX {
+                    dist[w] = dist[v] + 1;
+                    que.push_back(w);
+                }
+                if dist[w] == dist[v] + 1 {
+                    sigma[w] += sigma[v];
+                    preds[w].push(v);
+                }
+            });
+        }
+        accumulate(&mut res, s, &order, &preds, &sigma);
+    }
+    halve_if_undirected(g, res)
+}
+
+// Betweenness over weighted shortest paths, by a Dijkstra per source;
+// weights must be positive so that a vertex settles after all its
+// predecessors on shortest paths. O(VE log V).
+pub fn weighted_betweenness<G: Graph + ?Sized>(g: &G) -> Vec<f64> {
+    assert!(
+        g.edges()
+            .iter()
+            .all(|e| e.2.cmp_weight(&G::Weight::zero()) == Ordering::Greater),
+        "edge weights must be positive"
+    );
+    let n = g.vertex_count();
+    let mut res = vec![0.0; n];
+    for s in 0..n {
+        let mut order = vec![];
+        let mut preds = vec![vec![]; n];
+        let mut sigma = vec![0.0; n];
+        let mut dist = vec![G::Weight::infinity(); n];
+        let mut settled = vec![false; n];
+        let mut heap = BinaryHeap::new();
+        sigma[s] = 1.0;
+        dist[s] = G::Weight::zero();
+        heap.push(Reverse((OrdWeight(dist[s]), s)));
+        while let Some(Reverse((_, v))) = heap.pop() {
+            if std::mem::replace(&mut settled[v], true) {
+                continue;
+            }
+            order.push(v);
+            g.for_each_neighbor(v, |w, c| {
+                let through = dist[v].saturating_plus(c);
+                match through.cmp_weight(&dist[w]) {
+                    Ordering::Less => {
+                        dist[w] = through;
+                        sigma[w] = sigma[v];
+                        preds[w] = vec![v];
+                        heap.push(Reverse((OrdWeight(through), w)));
+                    }
+                    Ordering::Equal if w != s => {
+                        sigma[w] += sigma[v];
+                        preds[w].push(v);
+                    }
+                    _ => {}
+                }
+            });
+        }
+        accumulate(&mut res, s, &order, &preds, &sigma);
+    }
+    halve_if_undirected(g, res)
+}
+
+// Local clustering coefficient of every vertex, ignoring edge directions:
+// the share of pairs of its neighbours that are adjacent themselves; 0 with
+// fewer than two neighbours.
+pub fn clustering<G: Graph + ?Sized>(g: &G) -> Vec<f64> {
+    let n = g.vertex_count();
+    let mut adj: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    for (u, v, _) in g.edges() {
+        if u != v {
+            adj[u].insert(v);
+            adj[v].insert(u);
+        }
+    }
+    (0..n)
+        .map(|v| {
+            let k = adj[v].len();
+            if k < 2 {
+                return 0.0;
+            }
+            let links = adj[v]
+                .iter()
+                .map(|a| adj[*a].iter().filter(|b| adj[v].contains(b)).count())
+                .sum::<usize>();
+            // every link was seen from both ends
+            links as f64 / (k * (k - 1)) as f64
+        })
+        .collect()
+}
+
+// Mean of the local clustering coefficients.
+pub fn average_clustering<G: Graph + ?Sized>(g: &G) -> f64 {
+    let c = clustering(g);
+    if c.is_empty() {
+        return 0.0;
+    }
+    c.iter().sum::<f64>() / c.len() as f64
+}
+
+// Betweenness counts paths in edges, as the free function does; diameter
+// and closeness go by the edge weights, through `weighted_distances`.
+impl<W: Weight> DictetedGraph<W> {
+    pub fn betweenness(&self) -> Vec<f64> {
+        betweenness(self)
+    }
+
+    pub fn closeness(&self) -> Vec<f64> {
+        closeness(&weighted_distances(self))
+    }
+
+    pub fn clustering(&self) -> Vec<f64> {
+        clustering(self)
+    }
+
+    pub fn diameter(&self) -> W {
+        diameter(&weighted_distances(self))
+    }
+}
+
+impl<W: Weight> UndiGraph<W> {
+    pub fn betweenness(&self) -> Vec<f64> {
+        betweenness(self)
+    }
+
+    pub fn closeness(&self) -> Vec<f64> {
+        closeness(&weighted_distances(self))
+    }
+
+    pub fn clustering(&self) -> Vec<f64> {
+        clustering(self)
+    }
+
+    pub fn diameter(&self) -> W {
+        diameter(&weighted_distances(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{lcg, random_edges};
+
+    #[test]
+    fn distances_on_a_path() {
+        let g = UndiGraph::build_graph(5, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 4, 1)]);
+        let d = hop_distances(&g);
+        assert_eq!(eccentricities(&d), vec![4, 3, 2, 3, 4]);
+        assert_eq!((diameter(&d), radius(&d)), (4, 2));
+        assert_eq!(center(&d), vec![2]);
+        assert_eq!(periphery(&d), vec![0, 4]);
+        assert_eq!(degree_distribution(&g), vec![0, 2, 3]);
+        assert!((closeness(&d)[2] - 4.0 / 6.0).abs() < 1e-12);
+        assert_eq!(betweenness(&g), vec![0.0, 3.0, 4.0, 3.0, 0.0]);
+        assert_eq!(g.betweenness(), betweenness(&g));
+        assert_eq!((g.diameter(), g.closeness()), (4, closeness(&d)));
+
+        // 3 can't reach anything, so only the diameter notices
+        let g = DictetedGraph::build_graph(4, vec![(0, 1, 2), (1, 2, 2), (2, 0, 2), (2, 3, 5)]);
+        let d = weighted_distances(&g);
+        assert_eq!(diameter(&d), i32::MAX);
+        assert_eq!(radius(&d), 5);
+        assert_eq!(center(&d), vec![2]);
+        assert_eq!(closeness(&d)[3], 0.0);
+        assert_eq!((g.diameter(), g.closeness()), (i32::MAX, closeness(&d)));
+        // in hops, 3 is one edge further than the rest
+        assert_eq!(hop_distances(&g)[0], vec![0, 1, 2, 3]);
+
+        // 1 reaches 2 for free, and 2 reaches nothing
+        let g = DictetedGraph::build_graph(3, vec![(0, 1, 1), (1, 2, 0)]);
+        assert_eq!(g.closeness(), vec![1.0, f64::INFINITY, 0.0]);
+    }
+
+    // 0 - 1 - ... - (n - 1)
+    fn narrow_path(n: usize) -> UndiGraph<i8> {
+        UndiGraph::build_graph(n, (1..n).map(|v| (v - 1, v, 1)).collect())
+    }
+
+    #[test]
+    fn hop_counts_up_to_the_sentinel() {
+        // 126 hops is the most an i8 holds below its infinity sentinel
+        let d = hop_distances(&narrow_path(127));
+        assert_eq!(d[0][126], 126);
+        assert_eq!(diameter(&d), 126);
+    }
+
+    #[test]
+    #[should_panic(expected = "hop count doesn't fit")]
+    fn hop_counts_past_the_sentinel() {
+        hop_distances(&narrow_path(130));
+    }
+
+    // sigma[s][t]: the number of shortest s-t paths, in edges
+    fn path_counts(g: &DictetedGraph<i32>, d: &[Vec<i32>]) -> Vec<Vec<f64>> {
+        let n = g.V();
+        let mut sigma = vec![vec![0.0; n]; n];
+        for s in 0..n {
+            let mut by_distance: Vec<usize> = (0..n).filter(|&v| !d[s][v].is_infinite()).collect();
+            by_distance.sort_by_key(|&v| d[s][v]);
+            sigma[s][s] = 1.0;
+            for &v in by_distance.iter() {
+                for u in g.neighbors(v) {
+                    if d[s][u] == d[s][v] + 1 {
+                        sigma[s][u] += sigma[s][v];
+                    }
+                }
+            }
+        }
+        sigma
+    }
+
+    #[test]
+    fn brandes_matches_definition() {
+        let mut next = lcg(23);
+        for _ in 0..20 {
+            let n = 8;
+            let g = DictetedGraph::build_graph(n, random_edges(n, 16, &mut next, |_| 1));
+            let d = hop_distances(&g);
+            let sigma = path_counts(&g, &d);
+            let fast = betweenness(&g);
+            let weighted = weighted_betweenness(&g);
+            for v in 0..n {
+                let mut expected = 0.0;
+                for s in (0..n).filter(|&s| s != v) {
+                    for t in (0..n).filter(|&t| t != v && t != s && !d[s][t].is_infinite()) {
+                        if !d[s][v].is_infinite()
+                            && !d[v][t].is_infinite()
+                            && d[s][v] + d[v][t] == d[s][t]
+                        {
+                            expected += sigma[s][v] * sigma[v][t] / sigma[s][t];
+                        }
+                    }
+                }
+                assert!((fast[v] - expected).abs() < 1e-9);
+                assert!((weighted[v] - expected).abs() < 1e-9);
+            }
+        }
+
+        // 0-3 ties between the direct edge and the way round
+        let g = UndiGraph::build_graph(4, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 3)]);
+        assert_eq!(weighted_betweenness(&g), vec![0.0, 1.5, 1.5, 0.0]);
+    }
+
+    #[test]
+    fn clustering_coefficients() {
+        // a triangle with a tail: 2 has neighbours 0, 1, 3 and one link
+        let g = UndiGraph::build_graph(4, vec![(0, 1, 1), (1, 2, 1), (2, 0, 1), (2, 3, 1)]);
+        assert_eq!(clustering(&g), vec![1.0, 1.0, 1.0 / 3.0, 0.0]);
+        assert_eq!(g.clustering(), clustering(&g));
+        assert!((average_clustering(&g) - 7.0 / 12.0).abs() < 1e-12);
+
+        // direction is ignored and a two-way pair is one link
+        let g = DictetedGraph::build_graph(3, vec![(0, 1, 1), (1, 0, 1), (0, 2, 1), (2, 1, 1)]);
+        assert_eq!(clustering(&g), vec![1.0, 1.0, 1.0]);
+    }
+}
@@ -0,0 +1,222 @@
+// PageRank by power iteration over any `Graph`. A random surfer follows a
+// uniformly chosen out-edge with probability `damping` and otherwise
+// teleports: to any vertex for plain PageRank, to one of the seeds for
+// personalized PageRank. A dangling vertex, with no out-edges, always
+// teleports. Parallel edges count once each; an undirected edge leads both
+// ways.
+use crate::directed_graph::DictetedGraph;
+use crate::traits::Graph;
+use crate::weight::Weight;
+
+#[derive(Debug, Clone)]
+pub struct PageRank {
+    scores: Vec<f64>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl PageRank {
+    // The stationary probability of every vertex; they add up to one.
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    pub fn score(&self, v: usize) -> f64 {
+        self.scores[v]
+    }
+
+    // Vertices from the highest score down; ties by index.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.scores.len()).collect();
+        order.sort_by(|&a, &b| self.scores[b].total_cmp(&self.scores[a]).then(a.cmp(&b)));
+        order
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    // False if `max_iterations` ran out before the change between two
+    // rounds dropped to `tolerance`.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+// Iterates until the L1 change of the scores is at most `tolerance`, or
+// `max_iterations` times. 0.85 is the usual damping.
+pub fn pagerank<G: Graph + ?Sized>(
+    g: &G,
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> PageRank {
+    let n = g.vertex_count();
+    let teleport = vec![1.0 / n as f64; n];
+    power_iteration(g, &teleport, damping, tolerance, max_iterations)
+}
+
+// PageRank that teleports back to `seeds` only, ranking vertices by their
+// closeness to them; vertices the seeds can't reach score 0.
+pub fn personalized_pagerank<G: Graph + ?Sized>(
+    g: &G,
+    seeds: &[usize],
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> PageRank {
+    assert!(!seeds.is_empty(), "personalized PageRank needs a seed");
+    let mut teleport = vec![0.0; g.vertex_count()];
+    for &s in seeds {
+        teleport[s] = 1.0;
+    }
+    let total: f64 = teleport.iter().sum();
+    teleport.iter_mut().for_each(|t| *t /= total);
+    power_iteration(g, &teleport, damping, tolerance, max_iterations)
+}
+
+// x = damping * (M x + dangling mass * t) + (1 - damping) * t for the
+// teleport distribution t, from x = t.
+pub fn power_iteration<G: Graph + ?Sized>(
+    g: &G,
+    teleport: &[f64],
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> PageRank {
+    assert!(
+        (0.0..=1.0).contains(&damping),
+        "damping must be between 0 and 1"
+    );
+    let n = g.vertex_count();
+    assert_eq!(teleport.len(), n, "one teleport probability per vertex");
+    let mut scores = teleport.to_vec();
+    let mut next = vec![0.0; n];
+    let mut iterations = 0;
+    let mut converged = n == 0;
+    while !converged && iterations < max_iterations {
+        // mass of the dangling vertices, spread like a teleport
+        let mut dangling = 0.0;
+        next.iter_mut().for_each(|x| *x = 0.0);
+        for (u, &x) in scores.iter().enumerate() {
+            let mut out = 0;
+            g.for_each_neighbor(u, |_, _| out += 1);
+            if out == 0 {
+                dangling += x;
+                continue;
+            }
+            let share = x / out as f64;
+            g.for_each_neighbor(u, |w, _| next[w] += share);
+        }
+        for (x, t) in next.iter_mut().zip(teleport) {
+            *x = damping * (*x + dangling * t) + (1.0 - damping) * t;
+        }
+
+        let change: f64 = scores
+            .iter()
+            .zip(next.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        std::mem::swap(&mut scores, &mut next);
+        iterations += 1;
+        converged = change <= tolerance;
+    }
+    PageRank {
+        scores,
+        iterations,
+        converged,
+    }
+}
+
+impl<W: Weight> DictetedGraph<W> {
+    pub fn pagerank(&self, damping: f64, tolerance: f64, max_iterations: usize) -> PageRank {
+        pagerank(self, damping, tolerance, max_iterations)
+    }
+
+    pub fn personalized_pagerank(
+        &self,
+        seeds: &[usize],
+        damping: f64,
+        tolerance: f64,
+        max_iterations: usize,
+    ) -> PageRank {
+        personalized_pagerank(self, seeds, damping, tolerance, max_iterations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x = d M x + (1 - d) t, with M the column-stochastic transition
+    // matrix, checked entry by entry
+    fn is_stationary(g: &DictetedGraph<i32>, rank: &PageRank, teleport: &[f64], d: f64) -> bool {
+        let n = g.V();
+        let x = rank.scores();
+        let mut expected: Vec<f64> = teleport.iter().map(|t| (1.0 - d) * t).collect();
+        for (u, &xu) in x.iter().enumerate() {
+            let out = g.get_vertex(u).borrow().iter().len();
+            for v in 0..n {
+                let m = if out == 0 {
+                    teleport[v]
+                } else {
+                    let edges = g
+                        .get_vertex(u)
+                        .borrow()
+                        .iter()
+                        .filter(|w| w.borrow().idx() == v)
+                        .count();
+                    edges as f64 / out as f64
+                };
+                expected[v] += d * m * xu;
+            }
+        }
+        expected.iter().zip(x).all(|(a, b)| (a - b).abs() < 1e-8)
+    }
+
+    #[test]
+    fn pagerank_is_stationary() {
+        // 3 is dangling; 0 -> 1 twice
+        let edges = vec![
+            (0, 1, 1),
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 2, 1),
+            (2, 0, 1),
+            (2, 3, 1),
+            (4, 2, 1),
+        ];
+        let g = DictetedGraph::build_graph(5, edges);
+        let rank = g.pagerank(0.85, 1e-12, 1000);
+        assert!(rank.converged());
+        assert!((rank.scores().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(is_stationary(&g, &rank, &[0.2; 5], 0.85));
+        assert_eq!(rank.ranking()[0], 2);
+        assert_eq!(*rank.ranking().last().unwrap(), 4);
+
+        let capped = g.pagerank(0.85, 1e-12, 3);
+        assert!(!capped.converged());
+        assert_eq!(capped.iterations(), 3);
+
+        // a cycle is uniform
+        let cycle = DictetedGraph::build_graph(4, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1)]);
+        let rank = cycle.pagerank(0.85, 1e-12, 100);
+        assert!(rank.scores().iter().all(|&x| (x - 0.25).abs() < 1e-12));
+    }
+
+    #[test]
+    fn personalized_pagerank_favours_the_seeds() {
+        let edges = vec![(0, 1, 1), (1, 2, 1), (2, 0, 1), (2, 3, 1), (4, 0, 1)];
+        let g = DictetedGraph::build_graph(5, edges);
+        let rank = g.personalized_pagerank(&[3], 0.85, 1e-12, 1000);
+        assert!(rank.converged());
+        assert!(is_stationary(&g, &rank, &[0.0, 0.0, 0.0, 1.0, 0.0], 0.85));
+        // 3 is dangling, so the surfer never leaves it
+        assert!((rank.score(3) - 1.0).abs() < 1e-9);
+
+        let rank = g.personalized_pagerank(&[0, 4], 0.85, 1e-12, 1000);
+        assert!(is_stationary(&g, &rank, &[0.5, 0.0, 0.0, 0.0, 0.5], 0.85));
+        assert!((rank.scores().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(rank.ranking()[0], 0);
+    }
+}
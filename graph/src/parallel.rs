@@ -0,0 +1,206 @@
+// Multithreaded queries over graphs that can be shared read-only between
+// threads: `CsrGraph`, dense matrices and `AdjGraph`, or views of them.
+// `DictetedGraph` and `UndiGraph` are built on `Rc`, so freeze them into a
+// `CsrGraph` first.
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use crate::search::{self, ShortestPaths};
+use crate::traits::Graph;
+
+pub use crate::matrix_graph::default_threads;
+
+// Frontiers smaller than this are expanded on the calling thread.
+const MIN_CHUNK: usize = 256;
+
+// Level-synchronous BFS: each level's frontier is split between `threads`
+// workers, which claim undiscovered vertices with a compare-and-swap on
+// their depth. Depths match the sequential search; the ancestor of a vertex
+// is whichever frontier vertex claimed it first.
+pub fn par_breadth_first_search<G>(
+    g: &G,
+    source: usize,
+    threads: usize,
+) -> (Vec<u32>, Vec<Option<usize>>)
+where
+    G: Graph + Sync + ?Sized,
+{
+    let n = g.vertex_count();
+    let depth: Vec<AtomicU32> = (0..n).map(|_| AtomicU32::new(u32::MAX)).collect();
+    let ancestor: Vec<AtomicUsize> = (0..n).map(|_| AtomicUsize::new(usize::MAX)).collect();
+
+    let expand = |part: &[usize], level: u32| {
+        let mut found = vec![];
+        for &v in part {
+            g.for_each_neighbor(v, |u, _| {
+                if depth[u]
+                    .compare_exchange(u32::MAX, level + 1, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    ancestor[u].store(v, Ordering::Relaxed);
+                    found.push(u);
+                }
+            });
+        }
+        found
+    };
+
+    depth[source].store(0, Ordering::Relaxed);
+    let mut frontier = vec![source];
+    let mut level = 0;
+    while !frontier.is_empty() {
+        let chunk = frontier.len().div_ceil(threads.max(1)).max(MIN_CHUNK);
+        frontier = if chunk >= frontier.len() {
+            expand(&frontier, level)
+        } else {
+            let expand = &expand;
+            std::thread::scope(|s| {
+                let workers: Vec<_> = frontier
+                    .chunks(chunk)
+                    .map(|part| s.spawn(move || expand(part, level)))
+                    .collect();
+                workers
+                    .into_iter()
+                    .flat_map(|w| w.join().unwrap())
+                    .collect()
+            })
+        };
+        level += 1;
+    }
+
+    let depth = depth.into_iter().map(AtomicU32::into_inner).collect();
+    let ancestor = ancestor
+        .into_iter()
+        .map(|a| Some(a.into_inner()).filter(|&a| a != usize::MAX))
+        .collect();
+    (depth, ancestor)
+}
+
+// Runs `query(g, s)` for every source on `threads` workers that pull the
+// next source as they finish; results come back in `sources` order.
+pub fn par_map_sources<G, R, F>(g: &G, sources: &[usize], threads: usize, query: F) -> Vec<R>
+where
+    G: Graph + Sync + ?Sized,
+    R: Send,
+    F: Fn(&G, usize) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let (next, query) = (&next, &query);
+    let mut results: Vec<(usize, R)> = std::thread::scope(|s| {
+        let workers: Vec<_> = (0..threads.max(1).min(sources.len()))
+            .map(|_| {
+                s.spawn(move || {
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= sources.len() {
+                            return done;
+                        }
+                        done.push((i, query(g, sources[i])));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+pub fn par_dijkstra_many<G>(
+    g: &G,
+    sources: &[usize],
+    threads: usize,
+) -> Vec<ShortestPaths<G::Weight>>
+where
+    G: Graph + Sync + ?Sized,
+{
+    par_map_sources(g, sources, threads, |g, s| search::dijkstra(g, s))
+}
+
+pub fn par_breadth_first_search_many<G>(
+    g: &G,
+    sources: &[usize],
+    threads: usize,
+) -> Vec<(Vec<u32>, Vec<Option<usize>>)>
+where
+    G: Graph + Sync + ?Sized,
+{
+    par_map_sources(g, sources, threads, |g, s| {
+        search::breadth_first_search(g, s)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrGraph;
+    use crate::test_util::{lcg, random_edges};
+
+    fn random_graph(n: usize, m: usize, seed: u64) -> CsrGraph<u32> {
+        let edges = random_edges(n, m, &mut lcg(seed), |x| (x % 100) as u32);
+        CsrGraph::from_edges(n, &edges)
+    }
+
+    #[test]
+    fn parallel_bfs_matches_sequential() {
+        let g = random_graph(20000, 80000, 3);
+        for &threads in &[1, 4] {
+            let (depth, ancestor) = par_breadth_first_search(&g, 0, threads);
+            assert_eq!(depth, search::breadth_first_search(&g, 0).0);
+            for (v, a) in ancestor.iter().enumerate() {
+                if let Some(a) = *a {
+                    assert_eq!(depth[a] + 1, depth[v]);
+                    assert!(g.neighbors(a).contains(&v));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn many_sources_in_order() {
+        let g = random_graph(500, 2000, 11);
+        let sources = [7, 0, 499, 7, 123];
+        let res = par_dijkstra_many(&g, &sources, 3);
+        for (&s, r) in sources.iter().zip(res.iter()) {
+            assert_eq!(r.0, search::dijkstra(&g, s).0);
+        }
+        let bfs = par_breadth_first_search_many(&g, &sources, 8);
+        assert_eq!(bfs[2], search::breadth_first_search(&g, 499));
+        assert!(par_dijkstra_many(&g, &[], 2).is_empty());
+    }
+
+    #[test]
+    fn voronoi_picks_nearest_source() {
+        let g = random_graph(300, 1200, 5);
+        let sources = [4, 90, 17];
+        let voronoi = search::multi_source_dijkstra(&g, &sources);
+        let single = par_dijkstra_many(&g, &sources, 2);
+        for v in 0..g.V() {
+            let best = sources
+                .iter()
+                .zip(single.iter())
+                .map(|(&s, (d, _))| (d[v], s))
+                .min()
+                .unwrap();
+            if best.0 == u32::MAX {
+                assert_eq!(voronoi.nearest(v), None);
+            } else if sources.contains(&v) {
+                assert_eq!(voronoi.nearest(v), Some(v));
+            } else {
+                assert_eq!(
+                    (voronoi.distance(v), voronoi.nearest(v)),
+                    (best.0, Some(best.1))
+                );
+            }
+        }
+        let covered: usize = sources.iter().map(|&s| voronoi.cell(s).len()).sum();
+        assert_eq!(
+            covered,
+            (0..g.V()).filter(|&v| voronoi.nearest(v).is_some()).count()
+        );
+    }
+}
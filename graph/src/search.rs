@@ -0,0 +1,607 @@
+// Traversals and single-source shortest paths, written once against the
+// `Graph` trait. The representations' own methods delegate here.
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+use fibonacii_heap::{AddressableHeap, IndexedBinaryHeap};
+use trace::{Event, NoTrace, Trace, Value};
+
+use crate::dfs::DfsEvents;
+use crate::traits::{Color, Graph};
+use crate::weight::{OrdWeight, Weight};
+
+// Distance to every vertex and its parent in the shortest-path tree.
+pub type ShortestPaths<W> = (Vec<W>, Vec<Option<usize>>);
+
+// Infinite distances are traced as null.
+fn weight_value<W: Weight>(w: W) -> Value {
+    if w.is_infinite() {
+        Value::Null
+    } else {
+        Value::Float(w.to_f64())
+    }
+}
+
+fn color_event(v: usize, color: Color) -> Event {
+    Event::new("color").with("v", v).with("color", color.name())
+}
+
+// Depths are u32::MAX for unreachable vertices.
+pub fn breadth_first_search<G>(g: &G, source: usize) -> (Vec<u32>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+{
+    breadth_first_search_traced(g, source, &mut NoTrace)
+}
+
+// Reports "enqueue" and "dequeue" for the queue and "color" as vertices
+// turn gray (discovered) and black (scanned).
+pub fn breadth_first_search_traced<G, T>(
+    g: &G,
+    source: usize,
+    trace: &mut T,
+) -> (Vec<u32>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+    T: Trace,
+{
+    let mut depth = vec![u32::MAX; g.vertex_count()];
+    let mut ancestor = vec![None; g.vertex_count()];
+    let mut que = VecDeque::new();
+
+    depth[source] = 0;
+    que.push_back(source);
+    trace.emit(|| color_event(source, Color::Gray));
+    trace.emit(|| Event::new("enqueue").with("v", source).with("depth", 0));
+    while let Some(v) = que.pop_front() {
+        trace.emit(|| Event::new("dequeue").with("v", v));
+        g.for_each_neighbor(v, |u, _| {
+            if depth[u] == u32::MAX {
+                depth[u] = depth[v] + 1;
+                ancestor[u] = Some(v);
+                que.push_back(u);
+                trace.emit(|| color_event(u, Color::Gray));
+                trace.emit(|| Event::new("enqueue").with("v", u).with("depth", depth[u]));
+            }
+        });
+        trace.emit(|| color_event(v, Color::Black));
+    }
+
+    (depth, ancestor)
+}
+
+// Starts a new tree at each still undiscovered vertex of `roots`, in order.
+pub fn dfs_events_with_roots<G>(g: &G, roots: Vec<usize>) -> DfsEvents
+where
+    G: Graph + ?Sized,
+{
+    let adj = (0..g.vertex_count()).map(|v| g.neighbors(v)).collect();
+    DfsEvents::new(adj, g.is_directed(), roots)
+}
+
+pub fn dfs_events<G: Graph + ?Sized>(g: &G) -> DfsEvents {
+    dfs_events_with_roots(g, (0..g.vertex_count()).collect())
+}
+
+pub fn dfs_events_from<G: Graph + ?Sized>(g: &G, start: usize) -> DfsEvents {
+    dfs_events_with_roots(g, vec![start])
+}
+
+// Dijkstra that skips the edges `skip(from, to)` rejects, and stops early
+// once `goal` is settled.
+pub(crate) fn dijkstra_filtered<G, H, S, T>(
+    g: &G,
+    source: usize,
+    goal: Option<usize>,
+    skip: S,
+    trace: &mut T,
+) -> (Vec<G::Weight>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+    H: AddressableHeap<OrdWeight<G::Weight>>,
+    S: Fn(usize, usize) -> bool,
+    T: Trace,
+{
+    let n = g.vertex_count();
+    let mut distance = vec![G::Weight::infinity(); n];
+    let mut ancestor = vec![None; n];
+    let mut heap = H::with_capacity(n);
+
+    distance[source] = G::Weight::zero();
+    heap.push(source, OrdWeight(G::Weight::zero()));
+    trace.emit(|| {
+        Event::new("enqueue")
+            .with("v", source)
+            .with("key", weight_value(G::Weight::zero()))
+    });
+
+    while let Some((v, OrdWeight(dist))) = heap.pop() {
+        trace.emit(|| {
+            Event::new("dequeue")
+                .with("v", v)
+                .with("key", weight_value(dist))
+        });
+        if Some(v) == goal {
+            break;
+        }
+        g.for_each_neighbor(v, |u, weight| {
+            if skip(v, u) {
+                return;
+            }
+            let through = dist.saturating_plus(weight);
+            if through.cmp_weight(&distance[u]) == Ordering::Less {
+                trace.emit(|| relax_event(v, u, distance[u], through));
+                distance[u] = through;
+                ancestor[u] = Some(v);
+                if heap.decrease_key(u, OrdWeight(through)) {
+                    trace.emit(|| {
+                        Event::new("decrease_key")
+                            .with("v", u)
+                            .with("key", weight_value(through))
+                    });
+                } else {
+                    heap.push(u, OrdWeight(through));
+                    trace.emit(|| {
+                        Event::new("enqueue")
+                            .with("v", u)
+                            .with("key", weight_value(through))
+                    });
+                }
+            }
+        });
+    }
+
+    (distance, ancestor)
+}
+
+// Dijkstra over any addressable heap, e.g. `FibonacciHeap<_>` for the
+// O(E + V log V) bound of CLRS 24.3.
+pub fn dijkstra_with<G, H>(g: &G, source: usize) -> (Vec<G::Weight>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+    H: AddressableHeap<OrdWeight<G::Weight>>,
+{
+    dijkstra_filtered::<G, H, _, _>(g, source, None, |_, _| false, &mut NoTrace)
+}
+
+pub fn dijkstra<G>(g: &G, source: usize) -> (Vec<G::Weight>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+{
+    dijkstra_with::<G, IndexedBinaryHeap<_>>(g, source)
+}
+
+// Reports "enqueue", "decrease_key" and "dequeue" on the heap, and every
+// successful "relax" of an edge (u, v) with v's old and new distance.
+pub fn dijkstra_traced<G, T>(
+    g: &G,
+    source: usize,
+    trace: &mut T,
+) -> (Vec<G::Weight>, Vec<Option<usize>>)
+where
+    G: Graph + ?Sized,
+    T: Trace,
+{
+    dijkstra_filtered::<G, IndexedBinaryHeap<_>, _, _>(g, source, None, |_, _| false, trace)
+}
+
+pub(crate) fn relax_event<W: Weight>(u: usize, v: usize, old: W, new: W) -> Event {
+    Event::new("relax")
+        .with("u", u)
+        .with("v", v)
+        .with("old", weight_value(old))
+        .with("new", weight_value(new))
+}
+
+// Every vertex's nearest source (a graph Voronoi partition); ties go to the
+// smaller source id.
+#[derive(Debug, Clone)]
+pub struct Voronoi<W> {
+    nearest: Vec<Option<usize>>,
+    distance: Vec<W>,
+    ancestor: Vec<Option<usize>>,
+}
+
+impl<W: Weight> Voronoi<W> {
+    // None if no source reaches `v`.
+    pub fn nearest(&self, v: usize) -> Option<usize> {
+        self.nearest[v]
+    }
+
+    pub fn distance(&self, v: usize) -> W {
+        self.distance[v]
+    }
+
+    pub fn distances(&self) -> &[W] {
+        &self.distance
+    }
+
+    // Parent on the shortest path back to the nearest source.
+    pub fn ancestor(&self, v: usize) -> Option<usize> {
+        self.ancestor[v]
+    }
+
+    // The vertices whose nearest source is `source`.
+    pub fn cell(&self, source: usize) -> Vec<usize> {
+        (0..self.nearest.len())
+            .filter(|&v| self.nearest[v] == Some(source))
+            .collect()
+    }
+}
+
+// Dijkstra from all `sources` at once, keyed by (distance, source) so a
+// vertex is claimed by the closest source, the smaller one on ties.
+// Duplicate sources are ignored.
+pub fn multi_source_dijkstra<G>(g: &G, sources: &[usize]) -> Voronoi<G::Weight>
+where
+    G: Graph + ?Sized,
+{
+    let n = g.vertex_count();
+    let mut distance = vec![G::Weight::infinity(); n];
+    let mut nearest: Vec<Option<usize>> = vec![None; n];
+    let mut ancestor = vec![None; n];
+    let mut heap = IndexedBinaryHeap::with_capacity(n);
+
+    for &s in sources {
+        if nearest[s].is_none() {
+            distance[s] = G::Weight::zero();
+            nearest[s] = Some(s);
+            heap.push(s, (OrdWeight(G::Weight::zero()), s));
+        }
+    }
+
+    while let Some((v, (OrdWeight(dist), src))) = heap.pop() {
+        g.for_each_neighbor(v, |u, weight| {
+            let through = (OrdWeight(dist.saturating_plus(weight)), src);
+            let current = (OrdWeight(distance[u]), nearest[u].unwrap_or(usize::MAX));
+            // a source always keeps itself, even across zero-weight edges
+            if through < current && nearest[u] != Some(u) {
+                distance[u] = through.0 .0;
+                nearest[u] = Some(src);
+                ancestor[u] = Some(v);
+                if !heap.decrease_key(u, through) {
+                    heap.push(u, through);
+                }
+            }
+        });
+    }
+
+    Voronoi {
+        nearest,
+        distance,
+        ancestor,
+    }
+}
+
+// Err(v) if a negative cycle is reachable from `source`; `v` is a vertex
+// whose distance could still be lowered, so walking its ancestors V times
+// lands on the cycle.
+pub fn bellman_ford<G>(g: &G, source: usize) -> Result<ShortestPaths<G::Weight>, usize>
+where
+    G: Graph + ?Sized,
+{
+    bellman_ford_traced(g, source, &mut NoTrace)
+}
+
+// Reports every successful "relax", including the one in the final check
+// that exposes a negative cycle.
+pub fn bellman_ford_traced<G, T>(
+    g: &G,
+    source: usize,
+    trace: &mut T,
+) -> Result<ShortestPaths<G::Weight>, usize>
+where
+    G: Graph + ?Sized,
+    T: Trace,
+{
+    match bellman_ford_passes(g, source, trace) {
+        (paths, None) => Ok(paths),
+        (_, Some(v)) => Err(v),
+    }
+}
+
+// A negative cycle reachable from `source`, listed in edge order, or None
+// if there is none.
+pub fn negative_cycle<G>(g: &G, source: usize) -> Option<Vec<usize>>
+where
+    G: Graph + ?Sized,
+{
+    let ((_, ancestor), v) = bellman_ford_passes(g, source, &mut NoTrace);
+    let mut v = v?;
+    for _ in 0..g.vertex_count() {
+        v = ancestor[v].unwrap();
+    }
+    let mut cycle = vec![v];
+    let mut u = ancestor[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = ancestor[u].unwrap();
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+// The distances and ancestors after the V - 1 passes, and on a negative
+// cycle a vertex whose distance still dropped in the final check; the
+// ancestors are then as they were when it was found.
+pub(crate) fn bellman_ford_passes<G, T>(
+    g: &G,
+    source: usize,
+    trace: &mut T,
+) -> (ShortestPaths<G::Weight>, Option<usize>)
+where
+    G: Graph + ?Sized,
+    T: Trace,
+{
+    let n = g.vertex_count();
+    let edges = g.edges();
+    let mut distance = vec![G::Weight::infinity(); n];
+    let mut ancestor = vec![None; n];
+    distance[source] = G::Weight::zero();
+
+    let mut relax = |from: usize, to: usize, weight: G::Weight, distance: &mut Vec<G::Weight>| {
+        let through = distance[from].saturating_plus(weight);
+        through.cmp_weight(&distance[to]) == Ordering::Less && {
+            trace.emit(|| relax_event(from, to, distance[to], through));
+            distance[to] = through;
+            true
+        }
+    };
+
+    for _ in 1..n {
+        let mut changed = false;
+        for &(from, to, weight) in edges.iter() {
+            if relax(from, to, weight, &mut distance) {
+                ancestor[to] = Some(from);
+                changed = true;
+            }
+            if !g.is_directed() && relax(to, from, weight, &mut distance) {
+                ancestor[from] = Some(to);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    for &(from, to, weight) in edges.iter() {
+        if relax(from, to, weight, &mut distance) {
+            ancestor[to] = Some(from);
+            return ((distance, ancestor), Some(to));
+        }
+        if !g.is_directed() && relax(to, from, weight, &mut distance) {
+            ancestor[from] = Some(to);
+            return ((distance, ancestor), Some(from));
+        }
+    }
+
+    ((distance, ancestor), None)
+}
+
+// Tarjan's algorithm with an explicit stack, so deep graphs don't overflow
+// the call stack. Components come out in reverse topological order; for an
+// undirected graph they are the connected components.
+pub fn strongly_connected_components<G>(g: &G) -> Vec<Vec<usize>>
+where
+    G: Graph + ?Sized,
+{
+    let n = g.vertex_count();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    // (vertex, its neighbors, the next one to look at)
+    let mut call: Vec<(usize, Vec<usize>, usize)> = Vec::new();
+    let mut counter = 0;
+    let mut res = vec![];
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        call.push((root, g.neighbors(root), 0));
+        while let Some((v, neighbors, next)) = call.last_mut() {
+            let v = *v;
+            if let Some(&u) = neighbors.get(*next) {
+                *next += 1;
+                if index[u] == usize::MAX {
+                    index[u] = counter;
+                    low[u] = counter;
+                    counter += 1;
+                    stack.push(u);
+                    on_stack[u] = true;
+                    call.push((u, g.neighbors(u), 0));
+                } else if on_stack[u] {
+                    low[v] = low[v].min(index[u]);
+                }
+                continue;
+            }
+
+            call.pop();
+            if let Some(&(parent, _, _)) = call.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                let mut connected = vec![];
+                while let Some(u) = stack.pop() {
+                    on_stack[u] = false;
+                    connected.push(u);
+                    if u == v {
+                        break;
+                    }
+                }
+                res.push(connected);
+            }
+        }
+    }
+
+    res
+}
+
+// Follows `ancestor` back from `end`; the path is listed end first, like
+// `DictetedGraph::get_shortest_path`.
+pub fn path_to(ancestor: &[Option<usize>], end: usize) -> Vec<usize> {
+    let mut path = vec![end];
+    let mut v = end;
+    while let Some(u) = ancestor[v] {
+        if path.len() > ancestor.len() {
+            break;
+        }
+        path.push(u);
+        v = u;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::CsrGraph;
+    use crate::directed_graph::DictetedGraph;
+    use crate::undirected_graph::UndiGraph;
+    use trace::Recorder;
+
+    fn sample_edges() -> Vec<(usize, usize, i64)> {
+        vec![
+            (0, 1, 4),
+            (0, 2, 1),
+            (2, 1, 2),
+            (1, 3, 1),
+            (2, 3, 5),
+            (3, 4, 3),
+            (5, 0, 1),
+        ]
+    }
+
+    fn to_matrix(n: usize, edges: &[(usize, usize, i64)]) -> Vec<Vec<i64>> {
+        let mut mat = vec![vec![i64::MAX; n]; n];
+        for (i, row) in mat.iter_mut().enumerate() {
+            row[i] = 0;
+        }
+        for &(from, to, w) in edges {
+            mat[from][to] = w;
+        }
+        mat
+    }
+
+    #[test]
+    fn representations_agree() {
+        let edges = sample_edges();
+        let g = DictetedGraph::build_graph(6, edges.clone());
+        let csr = CsrGraph::from(&g);
+        let mat = to_matrix(6, &edges);
+
+        assert_eq!(g.edge_count(), 7);
+        assert_eq!(mat.edge_count(), 7);
+        assert_eq!(mat.edge_weight(2, 1), Graph::edge_weight(&g, 2, 1));
+
+        let bfs = breadth_first_search(&g, 0);
+        assert_eq!(bfs.0, vec![0, 1, 1, 2, 3, u32::MAX]);
+        assert_eq!(breadth_first_search(&csr, 0), bfs);
+        assert_eq!(breadth_first_search(mat.as_slice(), 0).0, bfs.0);
+
+        let expected = vec![0, 3, 1, 4, 7, i64::MAX];
+        assert_eq!(dijkstra(&g, 0).0, expected);
+        assert_eq!(dijkstra(&csr, 0).0, expected);
+        assert_eq!(dijkstra(mat.as_slice(), 0).0, expected);
+        let (distance, ancestor) = bellman_ford(mat.as_slice(), 0).unwrap();
+        assert_eq!(distance, expected);
+        assert_eq!(path_to(&ancestor, 4), vec![4, 3, 1, 2, 0]);
+
+        let events: Vec<_> = dfs_events(mat.as_slice()).collect();
+        assert_eq!(events, dfs_events(&g).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn bellman_ford_reports_negative_cycles() {
+        let mut edges = sample_edges();
+        edges.push((4, 2, -9));
+        let g = DictetedGraph::build_graph(6, edges);
+        let v = bellman_ford(&g, 0).unwrap_err();
+        assert!([1, 2, 3, 4].contains(&v));
+        assert!(bellman_ford(&g, 5).is_err());
+
+        // an undirected negative edge is a negative cycle by itself
+        let ug = UndiGraph::build_graph(3, vec![(0, 1, 2), (1, 2, -1)]);
+        assert!(bellman_ford(&ug, 0).is_err());
+        let ug = UndiGraph::build_graph(3, vec![(0, 1, 2), (1, 2, 1)]);
+        assert_eq!(bellman_ford(&ug, 2).unwrap().0, vec![3, 1, 0]);
+    }
+
+    #[test]
+    fn strongly_connected_components_on_every_representation() {
+        let mut edges = sample_edges();
+        edges.extend(vec![(3, 2, 1), (4, 3, 1)]);
+        let mut g = DictetedGraph::build_graph(6, edges);
+        let csr = CsrGraph::from(&g);
+        let sorted = |mut scc: Vec<Vec<usize>>| {
+            scc.iter_mut().for_each(|c| c.sort_unstable());
+            scc
+        };
+
+        // reverse topological order: the sink component first
+        let scc = sorted(strongly_connected_components(&g));
+        assert_eq!(scc, vec![vec![1, 2, 3, 4], vec![0], vec![5]]);
+        assert_eq!(sorted(csr.stronge_connected_components()), scc);
+        assert_eq!(sorted(g.tarjan_scc()), scc);
+        let mut kosaraju = sorted(g.stronge_connected_components());
+        kosaraju.reverse();
+        assert_eq!(kosaraju, scc);
+
+        // a long path doesn't overflow the call stack
+        let path: Vec<_> = (1..100_000).map(|v| (v - 1, v, 1)).collect();
+        let path = DictetedGraph::build_graph(100_000, path);
+        assert_eq!(strongly_connected_components(&path).len(), 100_000);
+
+        let ug = UndiGraph::build_graph(5, vec![(0, 1, 1), (1, 2, 1), (3, 4, 1)]);
+        let scc = sorted(strongly_connected_components(&ug));
+        assert_eq!(scc, vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn traced_runs_match_untraced() {
+        let g = DictetedGraph::build_graph(6, sample_edges());
+        let mut rec = Recorder::new();
+        assert_eq!(dijkstra_traced(&g, 0, &mut rec), dijkstra(&g, 0));
+        // 1 and 3 are both relaxed twice, first from 0 and 2, then from 2 and 1
+        let relaxed: Vec<_> = rec
+            .of_kind("relax")
+            .map(|e| (e.get("v").cloned(), e.get("new").cloned()))
+            .collect();
+        assert_eq!(relaxed.len(), 6);
+        assert_eq!(relaxed[2], (Some(Value::Int(1)), Some(Value::Float(3.0))));
+        assert_eq!(rec.of_kind("dequeue").count(), 5);
+        assert_eq!(rec.of_kind("decrease_key").count(), 2);
+        assert!(rec
+            .to_json_lines()
+            .starts_with("{\"step\":0,\"event\":\"enqueue\",\"v\":0,\"key\":0}\n"));
+
+        let mut rec = Recorder::new();
+        assert_eq!(
+            breadth_first_search_traced(&g, 5, &mut rec),
+            breadth_first_search(&g, 5)
+        );
+        let colors = rec.of_kind("color").count();
+        assert_eq!((colors, rec.of_kind("enqueue").count()), (12, 6));
+
+        let mut rec = Recorder::new();
+        assert!(bellman_ford_traced(&g, 0, &mut rec).is_ok());
+        assert!(rec
+            .of_kind("relax")
+            .all(|e| e.get("new") != Some(&Value::Null)));
+
+        let mut dag = DictetedGraph::build_graph(6, sample_edges());
+        let mut rec = Recorder::new();
+        dag.shortest_path_find_by_sort_traced(0, &mut rec);
+        let first = &rec.events()[0];
+        assert_eq!(first.get("old"), Some(&Value::Null));
+        let events: Vec<Event> = dfs_events(&g).map(Event::from).collect();
+        assert_eq!(events[1].get("kind"), Some(&Value::from("tree")));
+    }
+}
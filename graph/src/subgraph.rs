@@ -0,0 +1,390 @@
+// Views that derive a graph from another one without copying it, and set
+// operations that build a new graph from one or two others.
+use std::collections::HashMap;
+
+use crate::traits::{Graph, GraphMut};
+use crate::weight::Weight;
+
+// The subgraph induced by `vertices`, renumbered 0..vertices.len() in the
+// order given.
+pub struct InducedSubgraph<'a, G: ?Sized> {
+    base: &'a G,
+    vertices: Vec<usize>,
+    index: Vec<Option<usize>>,
+    e: usize,
+}
+
+impl<'a, G: Graph + ?Sized> InducedSubgraph<'a, G> {
+    // Duplicates in `vertices` are dropped.
+    pub fn new(base: &'a G, vertices: &[usize]) -> Self {
+        let mut index = vec![None; base.vertex_count()];
+        let mut kept = vec![];
+        for &v in vertices {
+            if index[v].is_none() {
+                index[v] = Some(kept.len());
+                kept.push(v);
+            }
+        }
+
+        let mut sub = InducedSubgraph {
+            base,
+            vertices: kept,
+            index,
+            e: 0,
+        };
+        sub.e = sub.edges().len();
+        sub
+    }
+
+    pub fn to_base(&self, v: usize) -> usize {
+        self.vertices[v]
+    }
+
+    // None if the base vertex isn't part of the subgraph.
+    pub fn from_base(&self, v: usize) -> Option<usize> {
+        self.index[v]
+    }
+}
+
+impl<'a, G: Graph + ?Sized> Graph for InducedSubgraph<'a, G> {
+    type Weight = G::Weight;
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.e
+    }
+
+    fn is_directed(&self) -> bool {
+        self.base.is_directed()
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, G::Weight),
+    {
+        self.base.for_each_neighbor(self.vertices[v], |u, w| {
+            if let Some(u) = self.index[u] {
+                f(u, w);
+            }
+        });
+    }
+}
+
+// The same vertices with only the edges `keep(from, to, weight)` accepts.
+// An undirected edge is always asked about with from <= to, so both of its
+// directions stay or go together.
+pub struct FilteredGraph<'a, G: ?Sized, P> {
+    base: &'a G,
+    keep: P,
+}
+
+impl<'a, G, P> FilteredGraph<'a, G, P>
+where
+    G: Graph + ?Sized,
+    P: Fn(usize, usize, G::Weight) -> bool,
+{
+    pub fn new(base: &'a G, keep: P) -> Self {
+        FilteredGraph { base, keep }
+    }
+
+    fn keeps(&self, from: usize, to: usize, w: G::Weight) -> bool {
+        if self.base.is_directed() {
+            (self.keep)(from, to, w)
+        } else {
+            (self.keep)(from.min(to), from.max(to), w)
+        }
+    }
+}
+
+impl<'a, G, P> Graph for FilteredGraph<'a, G, P>
+where
+    G: Graph + ?Sized,
+    P: Fn(usize, usize, G::Weight) -> bool,
+{
+    type Weight = G::Weight;
+
+    fn vertex_count(&self) -> usize {
+        self.base.vertex_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.base
+            .edges()
+            .into_iter()
+            .filter(|&(from, to, w)| self.keeps(from, to, w))
+            .count()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.base.is_directed()
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, G::Weight),
+    {
+        self.base.for_each_neighbor(v, |u, w| {
+            if self.keeps(v, u, w) {
+                f(u, w);
+            }
+        });
+    }
+}
+
+// Set operations. Each one fills `out`, which must start without vertices,
+// and returns it. Edges are compared by their endpoints only; parallel
+// edges count once, with their lightest weight.
+
+fn key(directed: bool, from: usize, to: usize) -> (usize, usize) {
+    if directed {
+        (from, to)
+    } else {
+        (from.min(to), from.max(to))
+    }
+}
+
+fn edge_set<G: Graph + ?Sized>(g: &G) -> HashMap<(usize, usize), G::Weight> {
+    let mut set: HashMap<(usize, usize), G::Weight> = HashMap::new();
+    for (from, to, w) in g.edges() {
+        let e = set.entry(key(g.is_directed(), from, to)).or_insert(w);
+        *e = e.min_weight(w);
+    }
+    set
+}
+
+fn fill<H: GraphMut>(mut out: H, v: usize, mut edges: Vec<((usize, usize), H::Weight)>) -> H {
+    assert_eq!(out.vertex_count(), 0, "output graph already has vertices");
+    for _ in 0..v {
+        out.push_vertex();
+    }
+    edges.sort_by_key(|&(e, _)| e);
+    for ((from, to), w) in edges {
+        out.add_edge(from, to, w);
+    }
+    out
+}
+
+// Copies any graph, e.g. a view, into a concrete representation.
+pub fn collect_into<G, H>(g: &G, out: H) -> H
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    let edges = g.edges().into_iter().map(|(u, v, w)| ((u, v), w)).collect();
+    fill(out, g.vertex_count(), edges)
+}
+
+// Every missing edge between two distinct vertices, with weight one.
+pub fn complement<G, H>(g: &G, out: H) -> H
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    let n = g.vertex_count();
+    let present = edge_set(g);
+    let mut edges = vec![];
+    for from in 0..n {
+        let first = if g.is_directed() { 0 } else { from + 1 };
+        for to in first..n {
+            if from != to && !present.contains_key(&(from, to)) {
+                edges.push(((from, to), G::Weight::one()));
+            }
+        }
+    }
+    fill(out, n, edges)
+}
+
+// On max(V) vertices; where both graphs have an edge, `a`'s weight wins.
+pub fn union<G, H>(a: &G, b: &G, out: H) -> H
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    assert_eq!(a.is_directed(), b.is_directed());
+    let mut set = edge_set(b);
+    set.extend(edge_set(a));
+    let n = a.vertex_count().max(b.vertex_count());
+    fill(out, n, set.into_iter().collect())
+}
+
+// On min(V) vertices, with `a`'s weights.
+pub fn intersection<G, H>(a: &G, b: &G, out: H) -> H
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    assert_eq!(a.is_directed(), b.is_directed());
+    let other = edge_set(b);
+    let edges = edge_set(a)
+        .into_iter()
+        .filter(|(e, _)| other.contains_key(e))
+        .collect();
+    fill(out, a.vertex_count().min(b.vertex_count()), edges)
+}
+
+// One vertex per edge of `g`, in `g.edges()` order, whose endpoints are
+// returned alongside. Undirected edges are adjacent when they share an end;
+// arc (u, v) leads to every arc (v, w). All weights are one.
+pub fn line_graph<G, H>(g: &G, out: H) -> (H, Vec<(usize, usize)>)
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    let ends: Vec<(usize, usize)> = g.edges().into_iter().map(|(u, v, _)| (u, v)).collect();
+    let mut incident = vec![vec![]; g.vertex_count()];
+    for (id, &(u, v)) in ends.iter().enumerate() {
+        incident[u].push(id);
+        if !g.is_directed() && u != v {
+            incident[v].push(id);
+        }
+    }
+
+    let mut edges = vec![];
+    for (id, &(u, v)) in ends.iter().enumerate() {
+        if g.is_directed() {
+            edges.extend(
+                incident[v]
+                    .iter()
+                    .map(|&next| ((id, next), G::Weight::one())),
+            );
+        } else {
+            let shared = if u == v { vec![u] } else { vec![u, v] };
+            for x in shared {
+                for &other in incident[x].iter().filter(|&&other| other > id) {
+                    edges.push(((id, other), G::Weight::one()));
+                }
+            }
+        }
+    }
+    edges.sort_by_key(|&(e, _)| e);
+    edges.dedup_by_key(|&mut (e, _)| e);
+    (fill(out, ends.len(), edges), ends)
+}
+
+// Vertex (x, y) is `x * b.vertex_count() + y`. It is joined to (x', y) by
+// every edge x-x' of `a` and to (x, y') by every edge y-y' of `b`, with their
+// weights.
+pub fn cartesian_product<G, H>(a: &G, b: &G, out: H) -> H
+where
+    G: Graph + ?Sized,
+    H: GraphMut<Weight = G::Weight>,
+{
+    assert_eq!(a.is_directed(), b.is_directed());
+    let (na, nb) = (a.vertex_count(), b.vertex_count());
+    let mut edges = vec![];
+    for (x1, x2, w) in a.edges() {
+        for y in 0..nb {
+            edges.push(((x1 * nb + y, x2 * nb + y), w));
+        }
+    }
+    for (y1, y2, w) in b.edges() {
+        for x in 0..na {
+            edges.push(((x * nb + y1, x * nb + y2), w));
+        }
+    }
+    fill(out, na * nb, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directed_graph::DictetedGraph;
+    use crate::search;
+    use crate::undirected_graph::UndiGraph;
+
+    fn sorted_edges<G: Graph>(g: &G) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> =
+            g.edges().into_iter().map(|(u, v, _)| (u, v)).collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn views_hide_vertices_and_edges() {
+        // a ring 0-1-2-3-4-0 with a chord 1-3
+        let edges = vec![
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 4, 1),
+            (4, 0, 1),
+            (1, 3, 5),
+        ];
+        let g: UndiGraph<i32> = UndiGraph::build_graph(5, edges);
+
+        let sub = g.induced_subgraph(&[3, 1, 2, 3]);
+        assert_eq!((sub.vertex_count(), sub.edge_count()), (3, 3));
+        assert_eq!(
+            (sub.to_base(0), sub.from_base(1), sub.from_base(0)),
+            (3, Some(1), None)
+        );
+        assert_eq!(search::breadth_first_search(&sub, 0).0, vec![0, 1, 1]);
+
+        let light = g.filtered(|_, _, w| w < 5);
+        assert_eq!(light.edge_count(), 5);
+        assert_eq!(search::dijkstra(&light, 1).0[3], 2);
+
+        let down = g.without_edges(&[(2, 1), (4, 3)]);
+        assert_eq!(down.edge_count(), 4);
+        assert_eq!(search::dijkstra(&down, 1).0, vec![1, 0, 6, 5, 2]);
+        let cut = collect_into(&down, UndiGraph::new(0));
+        assert_eq!(cut.E(), 4);
+        assert!(cut.is_connected());
+
+        let dg: DictetedGraph<i32> = DictetedGraph::build_graph(3, vec![(0, 1, 1), (1, 2, 1)]);
+        let down = dg.without_edges(&[(1, 2)]);
+        assert_eq!(search::breadth_first_search(&down, 0).0[2], u32::MAX);
+        assert_eq!(search::breadth_first_search(&dg, 0).0[2], 2);
+    }
+
+    #[test]
+    fn set_operations() {
+        let path: UndiGraph<i32> = UndiGraph::build_graph(4, vec![(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+        // P4 is self-complementary
+        assert_eq!(
+            sorted_edges(&path.complement()),
+            vec![(0, 2), (0, 3), (1, 3)]
+        );
+
+        let dg: DictetedGraph<i32> = DictetedGraph::build_graph(3, vec![(0, 1, 1), (1, 2, 1)]);
+        assert_eq!(
+            sorted_edges(&dg.complement()),
+            vec![(0, 2), (1, 0), (2, 0), (2, 1)]
+        );
+
+        let other: UndiGraph<i32> = UndiGraph::build_graph(5, vec![(1, 0, 9), (3, 4, 2)]);
+        let union = path.union(&other);
+        assert_eq!(union.V(), 5);
+        assert_eq!(sorted_edges(&union), vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+        assert_eq!(union.edge_weight(0, 1), Some(1));
+        let both = other.intersection(&path);
+        assert_eq!((both.V(), sorted_edges(&both)), (4, vec![(0, 1)]));
+        assert_eq!(both.edge_weight(1, 0), Some(9));
+    }
+
+    #[test]
+    fn line_graph_and_product() {
+        let star: UndiGraph<i32> = UndiGraph::build_graph(4, vec![(0, 1, 1), (0, 2, 1), (0, 3, 1)]);
+        let (line, ends) = star.line_graph();
+        assert_eq!(ends.len(), 3);
+        assert_eq!(sorted_edges(&line), vec![(0, 1), (0, 2), (1, 2)]);
+
+        let cycle: DictetedGraph<i32> =
+            DictetedGraph::build_graph(3, vec![(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+        let (line, ends) = cycle.line_graph();
+        assert_eq!(line.E(), 3);
+        for (a, b, _) in line.edges() {
+            assert_eq!(ends[a].1, ends[b].0);
+        }
+
+        let p3: UndiGraph<i32> = UndiGraph::build_graph(3, vec![(0, 1, 2), (1, 2, 2)]);
+        let p2: UndiGraph<i32> = UndiGraph::build_graph(2, vec![(0, 1, 5)]);
+        let grid = p3.cartesian_product(&p2);
+        assert_eq!((grid.V(), grid.E()), (6, 7));
+        // (0, 0) to (2, 1): two steps along p3 and one along p2
+        assert_eq!(search::dijkstra(&grid, 0).0[5], 9);
+    }
+}
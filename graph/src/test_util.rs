@@ -0,0 +1,54 @@
+// Fixtures shared by the unit tests: a seeded generator, so the random
+// cases checked against brute force are the same on every run, and the
+// random graphs built from it.
+use crate::directed_graph::DictetedGraph;
+use crate::undirected_graph::UndiGraph;
+use crate::weight::Weight;
+
+// Knuth's MMIX linear congruential generator; only the high bits are
+// random enough to use.
+pub fn lcg(seed: u64) -> impl FnMut() -> usize {
+    let mut state = seed;
+    move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    }
+}
+
+// `m` edges between random endpoints below `n`, self-loops and parallel
+// edges included; `weight` turns a random number into the edge weight.
+pub fn random_edges<W, F>(
+    n: usize,
+    m: usize,
+    next: &mut impl FnMut() -> usize,
+    weight: F,
+) -> Vec<(usize, usize, W)>
+where
+    F: Fn(usize) -> W,
+{
+    (0..m)
+        .map(|_| {
+            let (from, to) = (next() % n, next() % n);
+            (from, to, weight(next()))
+        })
+        .collect()
+}
+
+// `DictetedGraph` drops the self-loops.
+pub fn random_directed<W, F>(n: usize, m: usize, seed: u64, weight: F) -> DictetedGraph<W>
+where
+    W: Weight,
+    F: Fn(usize) -> W,
+{
+    DictetedGraph::build_graph(n, random_edges(n, m, &mut lcg(seed), weight))
+}
+
+pub fn random_undirected<W, F>(n: usize, m: usize, seed: u64, weight: F) -> UndiGraph<W>
+where
+    W: Weight,
+    F: Fn(usize) -> W,
+{
+    UndiGraph::build_graph(n, random_edges(n, m, &mut lcg(seed), weight))
+}
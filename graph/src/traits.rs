@@ -0,0 +1,89 @@
+use crate::weight::Weight;
+
+// Vertex state during a search, shared by every representation.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub(crate) enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+impl Color {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Color::White => "white",
+            Color::Gray => "gray",
+            Color::Black => "black",
+        }
+    }
+}
+
+// Read-only view of a graph that the algorithms in `search` are written
+// against. Vertices are `0..vertex_count()`. An undirected edge is seen from
+// both of its ends, a self-loop only once.
+pub trait Graph {
+    type Weight: Weight;
+
+    fn vertex_count(&self) -> usize;
+
+    fn edge_count(&self) -> usize;
+
+    fn is_directed(&self) -> bool;
+
+    // Calls `f(to, weight)` for every edge leaving `v`.
+    fn for_each_neighbor<F>(&self, v: usize, f: F)
+    where
+        F: FnMut(usize, Self::Weight);
+
+    fn neighbors(&self, v: usize) -> Vec<usize> {
+        let mut res = vec![];
+        self.for_each_neighbor(v, |u, _| res.push(u));
+        res
+    }
+
+    fn out_edges(&self, v: usize) -> Vec<(usize, Self::Weight)> {
+        let mut res = vec![];
+        self.for_each_neighbor(v, |u, w| res.push((u, w)));
+        res
+    }
+
+    // Every edge once; an undirected edge is listed from its smaller end.
+    fn edges(&self) -> Vec<(usize, usize, Self::Weight)> {
+        let mut res = vec![];
+        for v in 0..self.vertex_count() {
+            self.for_each_neighbor(v, |u, w| {
+                if self.is_directed() || v <= u {
+                    res.push((v, u, w));
+                }
+            });
+        }
+        res
+    }
+
+    // The lightest edge from `from` to `to`, if there is one.
+    fn edge_weight(&self, from: usize, to: usize) -> Option<Self::Weight> {
+        let mut res: Option<Self::Weight> = None;
+        self.for_each_neighbor(from, |u, w| {
+            if u == to {
+                res = Some(res.map_or(w, |r| r.min_weight(w)));
+            }
+        });
+        res
+    }
+}
+
+// Graphs that can grow a vertex or an edge at a time.
+pub trait GraphMut: Graph {
+    // Appends an isolated vertex and returns its id.
+    fn push_vertex(&mut self) -> usize;
+
+    // False if the representation rejects the edge.
+    fn add_edge(&mut self, from: usize, to: usize, weight: Self::Weight) -> bool;
+
+    // Whether `add_edge` would take the edge once both ends exist, so a
+    // caller can check before adding them; by default only NaN is refused.
+    fn accepts_edge(&self, from: usize, to: usize, weight: Self::Weight) -> bool {
+        let _ = (from, to);
+        weight.is_valid()
+    }
+}
@@ -0,0 +1,329 @@
+// 2-SAT by strongly connected components (CLRS exercise 34.4-7, after
+// Aspvall, Plass and Tarjan). A clause (a or b) is the two implications
+// !a -> b and !b -> a; the formula is unsatisfiable exactly when some x and
+// !x end up in the same component of that implication graph. Otherwise
+// setting x true when its component comes after !x's in topological order
+// satisfies every clause.
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::BufRead;
+
+use crate::directed_graph::DictetedGraph;
+
+// A variable, counted from 0, or its negation. Shown the DIMACS way: the
+// variable counted from 1, negative when negated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Literal {
+    var: usize,
+    negated: bool,
+}
+
+impl Literal {
+    pub fn pos(var: usize) -> Self {
+        Literal {
+            var,
+            negated: false,
+        }
+    }
+
+    pub fn neg(var: usize) -> Self {
+        Literal { var, negated: true }
+    }
+
+    // None for 0, which DIMACS uses to end a clause.
+    pub fn from_dimacs(lit: i64) -> Option<Self> {
+        let var = lit.unsigned_abs().checked_sub(1)? as usize;
+        Some(Literal {
+            var,
+            negated: lit < 0,
+        })
+    }
+
+    pub fn var(self) -> usize {
+        self.var
+    }
+
+    pub fn is_negated(self) -> bool {
+        self.negated
+    }
+
+    pub fn negate(self) -> Self {
+        Literal {
+            var: self.var,
+            negated: !self.negated,
+        }
+    }
+
+    // x is vertex 2x of the implication graph, !x is 2x + 1.
+    pub fn vertex(self) -> usize {
+        2 * self.var + self.negated as usize
+    }
+
+    pub fn is_true(self, assignment: &[bool]) -> bool {
+        assignment[self.var] != self.negated
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.negated { "-" } else { "" };
+        write!(f, "{}{}", sign, self.var + 1)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TwoSat {
+    vars: usize,
+    clauses: Vec<(Literal, Literal)>,
+}
+
+impl TwoSat {
+    pub fn new(vars: usize) -> Self {
+        TwoSat {
+            vars,
+            clauses: vec![],
+        }
+    }
+
+    pub fn var_count(&self) -> usize {
+        self.vars
+    }
+
+    pub fn clauses(&self) -> &[(Literal, Literal)] {
+        &self.clauses
+    }
+
+    // (a or b); a variable past `var_count()` adds the ones up to it.
+    pub fn add_clause(&mut self, a: Literal, b: Literal) {
+        self.vars = self.vars.max(a.var + 1).max(b.var + 1);
+        self.clauses.push((a, b));
+    }
+
+    // a must hold.
+    pub fn add_unit(&mut self, a: Literal) {
+        self.add_clause(a, a);
+    }
+
+    // a -> b
+    pub fn add_implication(&mut self, a: Literal, b: Literal) {
+        self.add_clause(a.negate(), b);
+    }
+
+    // Not both.
+    pub fn add_conflict(&mut self, a: Literal, b: Literal) {
+        self.add_clause(a.negate(), b.negate());
+    }
+
+    // 2 * var_count() vertices, see `Literal::vertex`. A tautology such as
+    // (x or !x) would be a self-loop and leaves no edge.
+    pub fn implication_graph(&self) -> DictetedGraph<i32> {
+        let mut g = DictetedGraph::new(2 * self.vars);
+        for &(a, b) in self.clauses.iter() {
+            g.add_edge(a.negate().vertex(), b.vertex(), 1);
+            if a != b {
+                g.add_edge(b.negate().vertex(), a.vertex(), 1);
+            }
+        }
+        g
+    }
+
+    // A value for every variable that satisfies all clauses, or a variable
+    // that is implied both ways, i.e. x and !x share a component.
+    pub fn solve(&self) -> Result<Vec<bool>, usize> {
+        // Tarjan lists the components in reverse topological order
+        let mut component = vec![0; 2 * self.vars];
+        for (c, members) in self.implication_graph().tarjan_scc().iter().enumerate() {
+            for &v in members {
+                component[v] = c;
+            }
+        }
+        (0..self.vars)
+            .map(|x| {
+                let (pos, neg) = (Literal::pos(x).vertex(), Literal::neg(x).vertex());
+                match component[pos].cmp(&component[neg]) {
+                    Ordering::Equal => Err(x),
+                    order => Ok(order == Ordering::Less),
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_satisfied_by(&self, assignment: &[bool]) -> bool {
+        self.clauses
+            .iter()
+            .all(|&(a, b)| a.is_true(assignment) || b.is_true(assignment))
+    }
+}
+
+fn invalid_line(line: usize, msg: String) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, msg),
+    )
+}
+
+// The 2-CNF part of DIMACS CNF: `c` comment lines, a `p cnf VARS CLAUSES`
+// header, then clauses as literals ended by 0, with one or two literals
+// each. A clause may span lines; a `%` line ends the input.
+pub fn parse_dimacs<R: BufRead>(reader: R) -> Result<TwoSat, std::io::Error> {
+    let mut header: Option<(usize, usize)> = None;
+    let mut sat = TwoSat::new(0);
+    let mut clause: Vec<Literal> = vec![];
+    let mut last_line = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        last_line = i + 1;
+        let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+        match fields[..] {
+            [] => continue,
+            ["%", ..] => break,
+            [first, ..] if first.starts_with('c') => continue,
+            ["p", "cnf", vars, clauses] if header.is_none() => {
+                let count = |field: &str| {
+                    field
+                        .parse::<usize>()
+                        .map_err(|_| invalid_line(i + 1, format!("invalid count `{}`", field)))
+                };
+                let vars = count(vars)?;
+                header = Some((vars, count(clauses)?));
+                sat = TwoSat::new(vars);
+                continue;
+            }
+            ["p", ..] => return Err(invalid_line(i + 1, format!("invalid header `{}`", line))),
+            _ => {}
+        }
+        let vars = match header {
+            Some((vars, _)) => vars,
+            None => {
+                return Err(invalid_line(
+                    i + 1,
+                    "expected the `p cnf` header".to_string(),
+                ))
+            }
+        };
+
+        for field in fields {
+            let lit = field
+                .parse::<i64>()
+                .map_err(|_| invalid_line(i + 1, format!("invalid literal `{}`", field)))?;
+            match Literal::from_dimacs(lit) {
+                Some(l) if l.var < vars => clause.push(l),
+                Some(_) => {
+                    return Err(invalid_line(
+                        i + 1,
+                        format!("variable {} out of range", lit.unsigned_abs()),
+                    ))
+                }
+                None => {
+                    match clause[..] {
+                        [a] => sat.add_unit(a),
+                        [a, b] => sat.add_clause(a, b),
+                        [] => return Err(invalid_line(i + 1, "empty clause".to_string())),
+                        _ => {
+                            return Err(invalid_line(
+                                i + 1,
+                                format!("clause of {} literals is not 2-CNF", clause.len()),
+                            ))
+                        }
+                    }
+                    clause.clear();
+                }
+            }
+        }
+    }
+
+    if !clause.is_empty() {
+        return Err(invalid_line(last_line, "clause not ended by 0".to_string()));
+    }
+    match header {
+        None => Err(invalid_line(
+            last_line,
+            "missing the `p cnf` header".to_string(),
+        )),
+        Some((_, clauses)) if clauses != sat.clauses.len() => Err(invalid_line(
+            last_line,
+            format!("expected {} clauses, found {}", clauses, sat.clauses.len()),
+        )),
+        Some(_) => Ok(sat),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut next = lcg(5);
+        let mut unsat = 0;
+        for _ in 0..200 {
+            let vars = 5;
+            let mut sat = TwoSat::new(vars);
+            let mut literal = || {
+                let x = next();
+                if x % 2 == 0 {
+                    Literal::pos(x / 2 % vars)
+                } else {
+                    Literal::neg(x / 2 % vars)
+                }
+            };
+            for _ in 0..8 {
+                let (a, b) = (literal(), literal());
+                sat.add_clause(a, b);
+            }
+            let satisfiable = (0..1u32 << vars).any(|mask| {
+                let assignment: Vec<bool> = (0..vars).map(|x| mask >> x & 1 == 1).collect();
+                sat.is_satisfied_by(&assignment)
+            });
+            match sat.solve() {
+                Ok(assignment) => assert!(sat.is_satisfied_by(&assignment)),
+                Err(x) => {
+                    assert!(!satisfiable);
+                    unsat += 1;
+                    let components = sat.implication_graph().tarjan_scc();
+                    let (pos, neg) = (Literal::pos(x).vertex(), Literal::neg(x).vertex());
+                    assert!(components
+                        .iter()
+                        .any(|c| c.contains(&pos) && c.contains(&neg)));
+                }
+            }
+        }
+        assert!(unsat > 0);
+    }
+
+    #[test]
+    fn parses_dimacs() {
+        let cnf = "\
+c a implies b, b implies not c, and a
+p cnf 3 3
+-1 2 0
+-2 -3
+0
+1 0
+";
+        let sat = parse_dimacs(cnf.as_bytes()).unwrap();
+        assert_eq!(sat.var_count(), 3);
+        assert_eq!(sat.clauses()[1], (Literal::neg(1), Literal::neg(2)));
+        assert_eq!(sat.solve(), Ok(vec![true, true, false]));
+        assert_eq!(Literal::neg(1).to_string(), "-2");
+
+        let mut contradiction = sat.clone();
+        contradiction.add_unit(Literal::pos(2));
+        assert_eq!(contradiction.solve(), Err(0));
+
+        let err = |cnf: &str| parse_dimacs(cnf.as_bytes()).unwrap_err().to_string();
+        assert_eq!(err("1 2 0\n"), "line 1: expected the `p cnf` header");
+        assert_eq!(
+            err("p cnf 3 1\n1 2 3 0\n"),
+            "line 2: clause of 3 literals is not 2-CNF"
+        );
+        assert_eq!(err("p cnf 2 1\n1 4 0\n"), "line 2: variable 4 out of range");
+        assert_eq!(err("p cnf 2 1\n1 x 0\n"), "line 2: invalid literal `x`");
+        assert_eq!(err("p cnf 2 1\n1 2\n"), "line 2: clause not ended by 0");
+        assert_eq!(
+            err("p cnf 2 2\n1 2 0\n"),
+            "line 2: expected 2 clauses, found 1"
+        );
+    }
+}
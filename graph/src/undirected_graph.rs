@@ -1,426 +1,1317 @@
-use std::cell::RefCell;
-use std::collections::BinaryHeap;
-use std::rc::Rc;
-
-extern crate disjoint_set;
-use disjoint_set::DSForest;
-#[derive(Eq, PartialEq, Debug)]
-enum Color {
-    White,
-    Gray,
-    Black,
-}
-
-type Vptr = Rc<RefCell<Vertex>>;
-type Vlist = Vec<Rc<RefCell<Vertex>>>;
-type Elist = Vec<(f32, Vptr)>;
-pub struct Vertex {
-    idx: usize,
-    visited: u8,
-    color: Color,
-    discover_time: u8,
-    finish_time: u8,
-    depth: u8,
-    ancestor: Option<Vptr>,
-    edges: Elist,
-}
-
-impl std::fmt::Display for Vertex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ac = self
-            .ancestor
-            .as_ref()
-            .map_or("Nan".to_string(), |v| format!("{}", v.borrow().idx));
-        write!(
-            f,
-            "{}: {:?}, dt {}, ft {}, depth {}, ancestor {}, edges {}",
-            self.idx,
-            self.color,
-            self.discover_time,
-            self.finish_time,
-            self.depth,
-            ac,
-            self.edges.len()
-        )
-    }
-}
-
-impl Eq for Vertex {
-    // fn eq(&self, other: &Self) -> bool {
-    //     self.idx == other.idx
-    // }
-}
-
-impl PartialEq for Vertex {
-    fn eq(&self, other: &Self) -> bool {
-        self.idx == other.idx
-    }
-}
-
-impl PartialOrd for Vertex {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Vertex {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.idx.cmp(&other.idx)
-    }
-}
-
-impl Vertex {
-    fn new(idx: usize) -> Self {
-        Vertex {
-            idx,
-            visited: 0,
-            color: Color::White,
-            discover_time: 0,
-            finish_time: 0,
-            depth: u8::MAX,
-            ancestor: None,
-            edges: Vec::<(f32, Vptr)>::new(),
-        }
-    }
-
-    pub fn new_vptr(idx: usize) -> Vptr {
-        Rc::new(RefCell::new(Vertex::new(idx)))
-    }
-
-    fn link(&mut self, weight: f32, v: Vptr) {
-        self.edges.push((weight, v));
-    }
-
-    fn reset_info(&mut self) {
-        self.visited = 0;
-        self.color = Color::White;
-        self.discover_time = 0;
-        self.finish_time = 0;
-        self.depth = u8::MAX;
-        self.ancestor = None;
-    }
-
-    #[allow(dead_code)]
-    fn is_visited(&self) -> bool {
-        self.visited >= 1
-    }
-
-    pub fn iter(&self) -> std::slice::Iter<'_, (f32, Rc<RefCell<Vertex>>)> {
-        self.edges.iter()
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct Edge {
-    v1: usize,
-    v2: usize,
-    weight: f32,
-}
-
-impl Edge {
-    pub fn new(v1: usize, v2: usize, weight: f32) -> Self {
-        Edge { v1, v2, weight }
-    }
-}
-
-impl Eq for Edge {}
-
-impl Ord for Edge {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.weight > other.weight {
-            std::cmp::Ordering::Less
-        } else {
-            std::cmp::Ordering::Greater
-        }
-    }
-}
-
-impl PartialOrd for Edge {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for Edge {
-    fn eq(&self, other: &Self) -> bool {
-        self.v1 == other.v1 && self.v2 == other.v2 || self.v1 == other.v2 && self.v2 == other.v1
-    }
-}
-
-impl std::fmt::Display for Edge {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {} {:2.3}", self.v1, self.v2, self.weight)
-    }
-}
-
-pub struct UndiGraph {
-    e: usize,
-    vertex_list: Vlist,
-    edge_collection: Vec<Edge>,
-}
-
-impl std::fmt::Display for UndiGraph {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for v in self.iter() {
-            write!(f, "{}: ", v.borrow().idx)?;
-            for (w, vv) in v.borrow().edges.iter() {
-                write!(f, "{} ({:.3}) ", vv.borrow().idx, w)?;
-            }
-            let _ = write!(f, "\n");
-        }
-
-        write!(f, "\n")
-    }
-}
-
-impl UndiGraph {
-    pub fn new(v: usize) -> UndiGraph {
-        let e = 0;
-        let mut vertex_list = Vec::new();
-        for i in 0..v {
-            vertex_list.push(Vertex::new_vptr(i));
-        }
-        UndiGraph {
-            e,
-            vertex_list,
-            edge_collection: Vec::new(),
-        }
-    }
-
-    pub fn build_graph(v: usize, edges: Vec<(usize, usize, f32)>) -> UndiGraph {
-        let mut g = UndiGraph::new(v);
-
-        for (from, to, w) in edges {
-            g.add_edge(from, to, w);
-        }
-
-        g
-    }
-
-    #[allow(non_snake_case)]
-    pub fn E(&self) -> usize {
-        self.e
-    }
-    #[allow(non_snake_case)]
-    pub fn V(&self) -> usize {
-        self.vertex_list.len()
-    }
-
-    pub fn get_vertex(&self, idx: usize) -> Vptr {
-        Rc::clone(&self.vertex_list[idx])
-    }
-
-    pub fn add_vertex(&mut self, v: Vptr) {
-        if v.borrow().idx >= self.V() {
-            self.vertex_list.push(v);
-        }
-    }
-
-    pub fn iter_edges(&self) -> std::slice::Iter<'_, Edge> {
-        self.edge_collection.iter()
-    }
-
-    pub fn add_edge(&mut self, v_idx: usize, u_idx: usize, weight: f32) -> bool {
-        if v_idx > self.V() || u_idx > self.V() {
-            return false;
-        }
-
-        let u = self.get_vertex(u_idx);
-        let v = self.get_vertex(v_idx);
-
-        u.borrow_mut().link(weight, self.get_vertex(v_idx));
-        if v_idx != u_idx {
-            v.borrow_mut().link(weight, u);
-        }
-
-        self.e += 1;
-        self.edge_collection.push(Edge::new(u_idx, v_idx, weight));
-        true
-    }
-
-    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex>>> {
-        self.vertex_list.iter()
-    }
-
-    pub fn reset_vertexs_info(&mut self) {
-        for v in self.iter() {
-            v.borrow_mut().reset_info();
-        }
-    }
-}
-
-impl UndiGraph {
-    pub fn breadth_first_search(&mut self, v_idx: usize) {
-        let mut que = std::collections::VecDeque::new();
-        let root = self.get_vertex(v_idx);
-        root.borrow_mut().color = Color::Gray;
-        root.borrow_mut().depth = 0;
-        que.push_back(root);
-
-        while !que.is_empty() {
-            let v = que.pop_front().unwrap();
-            let depth = v.borrow().depth;
-
-            for (_, u) in v.borrow().iter() {
-                if u.borrow().color == Color::White {
-                    u.borrow_mut().ancestor = Some(v.clone());
-                    u.borrow_mut().depth = depth + 1;
-                    u.borrow_mut().color = Color::Gray;
-                    que.push_back(u.clone());
-                }
-            }
-            v.borrow_mut().color = Color::Black;
-        }
-    }
-
-    pub fn query_depth(&mut self, from_idx: usize, to_idx: usize) -> u8 {
-        self.breadth_first_search(from_idx);
-        self.vertex_list[to_idx].borrow().depth
-    }
-
-    pub fn get_depth(&mut self, v_idx: usize) -> u8 {
-        self.vertex_list[v_idx].borrow().depth
-    }
-
-    pub fn get_ancestor(&self, v_idx: usize) -> Option<Vptr> {
-        self.vertex_list[v_idx].borrow().ancestor.clone()
-    }
-
-    pub fn depth_first_search(&mut self) {
-        let mut time = 0u8;
-        for v in self.iter() {
-            dfs_helper(v, &mut time);
-        }
-
-        fn dfs_helper(v: &Vptr, time: &mut u8) {
-            if v.borrow().color != Color::White {
-                return;
-            }
-
-            *time += 1;
-            v.borrow_mut().discover_time = *time;
-            v.borrow_mut().color = Color::Gray;
-            for (_, u) in v
-                .borrow()
-                .iter()
-                .filter(|(_, u)| u.borrow().color == Color::White)
-            {
-                u.borrow_mut().ancestor = Some(Rc::clone(v));
-                dfs_helper(u, time);
-            }
-
-            v.borrow_mut().color = Color::Black;
-            *time += 1;
-            v.borrow_mut().finish_time = *time;
-        }
-    }
-
-    pub fn topological_sort(&mut self) -> Vec<(usize, u8)> {
-        self.depth_first_search();
-
-        let mut order = Vec::new();
-        for v in self.iter() {
-            order.push((v.borrow().idx, v.borrow().finish_time));
-        }
-
-        // order.sort_by(|a, b| a.1.partial_cmp(b.1));
-
-        order
-    }
-
-    pub fn get_edges_heap_min(&self) -> BinaryHeap<&Edge> {
-        let mut heap = BinaryHeap::new();
-        for e in self.iter_edges() {
-            heap.push(e);
-        }
-        heap
-    }
-
-    // pub fn get_edges_heap_min(&self) -> BinaryHeap<Reverse<&Edge>> {
-    //     let mut heap = BinaryHeap::new();
-    //     for e in self.iter_edges() {
-    //         heap.push(Reverse(e));
-    //     }
-    //     heap
-    // }
-}
-
-// Minimum spinning trees
-impl UndiGraph {
-    pub fn kerskal_mst(&self) -> Vec<Edge> {
-        let mut heap = self.get_edges_heap_min();
-        let mut set = DSForest::new(self.V());
-        let mut mst = Vec::new();
-
-        while let Some(e) = heap.pop() {
-            let Edge { v1, v2, weight: _ } = e.clone();
-            if !set.is_linked(v1, v2) {
-                set.union(v1, v2);
-                mst.push(e.clone());
-            }
-
-            if set.forests() == 1 {
-                break;
-            }
-        }
-
-        mst
-    }
-
-    pub fn prim_mst(&self) -> Vec<Edge> {
-        let mut mst = Vec::new();
-        let mut heap = BinaryHeap::new();
-
-        let mut visited = vec![false; self.V()];
-        visited[0] = true;
-        let mut u_idx = 0;
-
-        while mst.len() != self.V() - 1 {
-            let u = self.get_vertex(u_idx);
-            for (weight, v) in u.borrow().iter() {
-                let v_idx = v.borrow().idx;
-                if !visited[v_idx] {
-                    heap.push(Edge::new(u_idx, v_idx, *weight));
-                }
-            }
-
-            while let Some(e) = heap.pop() {
-                let v2 = e.v2;
-                if !visited[v2] {
-                    visited[v2] = true;
-                    mst.push(e);
-                    u_idx = v2;
-                    break;
-                }
-            }
-        }
-
-        mst
-    }
-}
-
-pub fn parse_graph_from_stdio_by_edges() -> Result<UndiGraph, std::io::Error> {
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-
-    // println!("parse_graph_from_stdio:  {}", input);
-    let v = input.trim().parse::<usize>().unwrap();
-
-    input.clear();
-    std::io::stdin().read_line(&mut input)?;
-    let e = input.trim().parse::<usize>().unwrap();
-
-    let mut g = UndiGraph::new(v);
-    for _ in 0..e {
-        input.clear();
-        std::io::stdin().read_line(&mut input)?;
-        let nums: Vec<&str> = input.split_ascii_whitespace().collect();
-        g.add_edge(
-            nums[0].trim().parse::<usize>().unwrap(),
-            nums[1].trim().parse::<usize>().unwrap(),
-            nums[2].trim().parse::<f32>().unwrap(),
-        );
-    }
-
-    Ok(g)
-}
+use std::cell::RefCell;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::rc::Rc;
+
+extern crate disjoint_set;
+use disjoint_set::DSForest;
+use fibonacii_heap::{AddressableHeap, IndexedBinaryHeap};
+
+use crate::dfs::{DfsEvent, DfsEvents, EdgeKind};
+use crate::search;
+use crate::subgraph::{self, FilteredGraph, InducedSubgraph};
+use crate::traits::{Color, Graph, GraphMut};
+use crate::weight::{OrdWeight, Weight};
+
+type Vptr<W> = Rc<RefCell<Vertex<W>>>;
+type Vlist<W> = Vec<Rc<RefCell<Vertex<W>>>>;
+type Elist<W> = Vec<(W, Vptr<W>)>;
+pub struct Vertex<W = f32> {
+    idx: usize,
+    visited: u8,
+    color: Color,
+    discover_time: u32,
+    finish_time: u32,
+    depth: u32,
+    ancestor: Option<Vptr<W>>,
+    edges: Elist<W>,
+}
+
+impl<W> std::fmt::Display for Vertex<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ac = self
+            .ancestor
+            .as_ref()
+            .map_or("Nan".to_string(), |v| format!("{}", v.borrow().idx));
+        write!(
+            f,
+            "{}: {:?}, dt {}, ft {}, depth {}, ancestor {}, edges {}",
+            self.idx,
+            self.color,
+            self.discover_time,
+            self.finish_time,
+            self.depth,
+            ac,
+            self.edges.len()
+        )
+    }
+}
+
+impl<W> Eq for Vertex<W> {
+    // fn eq(&self, other: &Self) -> bool {
+    //     self.idx == other.idx
+    // }
+}
+
+impl<W> PartialEq for Vertex<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl<W> PartialOrd for Vertex<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W> Ord for Vertex<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.idx.cmp(&other.idx)
+    }
+}
+
+impl<W: Weight> Vertex<W> {
+    fn new(idx: usize) -> Self {
+        Vertex {
+            idx,
+            visited: 0,
+            color: Color::White,
+            discover_time: 0,
+            finish_time: 0,
+            depth: u32::MAX,
+            ancestor: None,
+            edges: Vec::<(W, Vptr<W>)>::new(),
+        }
+    }
+
+    pub fn new_vptr(idx: usize) -> Vptr<W> {
+        Rc::new(RefCell::new(Vertex::new(idx)))
+    }
+
+    fn link(&mut self, weight: W, v: Vptr<W>) {
+        self.edges.push((weight, v));
+    }
+
+    fn reset_info(&mut self) {
+        self.visited = 0;
+        self.color = Color::White;
+        self.discover_time = 0;
+        self.finish_time = 0;
+        self.depth = u32::MAX;
+        self.ancestor = None;
+    }
+
+    #[allow(dead_code)]
+    fn is_visited(&self) -> bool {
+        self.visited >= 1
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (W, Rc<RefCell<Vertex<W>>>)> {
+        self.edges.iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Edge<W = f32> {
+    v1: usize,
+    v2: usize,
+    weight: W,
+}
+
+impl<W: Weight> Edge<W> {
+    pub fn new(v1: usize, v2: usize, weight: W) -> Self {
+        Edge { v1, v2, weight }
+    }
+
+    pub fn ends(&self) -> (usize, usize) {
+        (self.v1, self.v2)
+    }
+
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+}
+
+impl<W: Weight> Eq for Edge<W> {}
+
+// Reversed on weight so a `BinaryHeap<Edge>` pops the lightest edge first;
+// ties are broken on the endpoints. Equality looks at the endpoints only, so
+// equal edges may still order apart when their weights differ.
+impl<W: Weight> Ord for Edge<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let ends = |e: &Self| (e.v1.min(e.v2), e.v1.max(e.v2));
+        other
+            .weight
+            .cmp_weight(&self.weight)
+            .then_with(|| ends(other).cmp(&ends(self)))
+    }
+}
+
+impl<W: Weight> PartialOrd for Edge<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> PartialEq for Edge<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.v1 == other.v1 && self.v2 == other.v2 || self.v1 == other.v2 && self.v2 == other.v1
+    }
+}
+
+impl<W: Weight> std::fmt::Display for Edge<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {:2.3}", self.v1, self.v2, self.weight)
+    }
+}
+
+pub struct UndiGraph<W = f32> {
+    e: usize,
+    vertex_list: Vlist<W>,
+    edge_collection: Vec<Edge<W>>,
+    // Kept in step with `add_edge` once incremental components are enabled.
+    components: Option<DSForest>,
+}
+
+impl<W: Weight> std::fmt::Display for UndiGraph<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for v in self.iter() {
+            write!(f, "{}: ", v.borrow().idx)?;
+            for (w, vv) in v.borrow().edges.iter() {
+                write!(f, "{} ({:.3}) ", vv.borrow().idx, w)?;
+            }
+            let _ = writeln!(f);
+        }
+
+        writeln!(f)
+    }
+}
+
+impl<W: Weight> UndiGraph<W> {
+    pub fn new(v: usize) -> UndiGraph<W> {
+        let e = 0;
+        let mut vertex_list = Vec::new();
+        for i in 0..v {
+            vertex_list.push(Vertex::new_vptr(i));
+        }
+        UndiGraph {
+            e,
+            vertex_list,
+            edge_collection: Vec::new(),
+            components: None,
+        }
+    }
+
+    pub fn build_graph(v: usize, edges: Vec<(usize, usize, W)>) -> UndiGraph<W> {
+        let mut g = UndiGraph::new(v);
+
+        for (from, to, w) in edges {
+            g.add_edge(from, to, w);
+        }
+
+        g
+    }
+
+    #[allow(non_snake_case)]
+    pub fn E(&self) -> usize {
+        self.e
+    }
+    #[allow(non_snake_case)]
+    pub fn V(&self) -> usize {
+        self.vertex_list.len()
+    }
+
+    pub fn get_vertex(&self, idx: usize) -> Vptr<W> {
+        Rc::clone(&self.vertex_list[idx])
+    }
+
+    pub fn add_vertex(&mut self, v: Vptr<W>) {
+        if v.borrow().idx >= self.V() {
+            if let Some(set) = self.components.as_mut() {
+                set.add(v.borrow().idx);
+            }
+            self.vertex_list.push(v);
+        }
+    }
+
+    pub fn iter_edges(&self) -> std::slice::Iter<'_, Edge<W>> {
+        self.edge_collection.iter()
+    }
+
+    // Out-of-range endpoints and NaN weights are rejected.
+    pub fn add_edge(&mut self, v_idx: usize, u_idx: usize, weight: W) -> bool {
+        if v_idx >= self.V() || u_idx >= self.V() || !weight.is_valid() {
+            return false;
+        }
+
+        let u = self.get_vertex(u_idx);
+        let v = self.get_vertex(v_idx);
+
+        u.borrow_mut().link(weight, self.get_vertex(v_idx));
+        if v_idx != u_idx {
+            v.borrow_mut().link(weight, u);
+        }
+
+        self.e += 1;
+        self.edge_collection.push(Edge::new(u_idx, v_idx, weight));
+        if let Some(set) = self.components.as_mut() {
+            if !set.is_linked(u_idx, v_idx) {
+                set.union(u_idx, v_idx);
+            }
+        }
+        true
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Rc<RefCell<Vertex<W>>>> {
+        self.vertex_list.iter()
+    }
+
+    pub fn reset_vertexs_info(&mut self) {
+        for v in self.iter() {
+            v.borrow_mut().reset_info();
+        }
+    }
+}
+
+impl<W: Weight> Graph for UndiGraph<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.V()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.E()
+    }
+
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn for_each_neighbor<F>(&self, v: usize, mut f: F)
+    where
+        F: FnMut(usize, W),
+    {
+        for (weight, u) in self.vertex_list[v].borrow().iter() {
+            f(u.borrow().idx, *weight);
+        }
+    }
+
+    fn edges(&self) -> Vec<(usize, usize, W)> {
+        self.iter_edges()
+            .map(|e| {
+                let (v1, v2) = e.ends();
+                (v1.min(v2), v1.max(v2), e.weight())
+            })
+            .collect()
+    }
+}
+
+impl<W: Weight> GraphMut for UndiGraph<W> {
+    fn push_vertex(&mut self) -> usize {
+        let idx = self.V();
+        self.add_vertex(Vertex::new_vptr(idx));
+        idx
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: W) -> bool {
+        UndiGraph::add_edge(self, from, to, weight)
+    }
+}
+
+impl<W: Weight> UndiGraph<W> {
+    // Records depth and ancestor on every vertex reached from `v_idx`.
+    pub fn breadth_first_search(&mut self, v_idx: usize) {
+        let (depth, ancestor) = search::breadth_first_search(self, v_idx);
+        for (v, d) in depth.into_iter().enumerate() {
+            if d == u32::MAX {
+                continue;
+            }
+            let mut v = self.vertex_list[v].borrow_mut();
+            v.depth = d;
+            v.ancestor = ancestor[v.idx].map(|u| self.get_vertex(u));
+            v.color = Color::Black;
+        }
+    }
+
+    pub fn query_depth(&mut self, from_idx: usize, to_idx: usize) -> u32 {
+        self.breadth_first_search(from_idx);
+        self.vertex_list[to_idx].borrow().depth
+    }
+
+    pub fn get_depth(&mut self, v_idx: usize) -> u32 {
+        self.vertex_list[v_idx].borrow().depth
+    }
+
+    pub fn get_ancestor(&self, v_idx: usize) -> Option<Vptr<W>> {
+        self.vertex_list[v_idx].borrow().ancestor.clone()
+    }
+
+    pub fn depth_first_search(&mut self) {
+        let mut time = 0u32;
+        for event in self.dfs_events() {
+            match event {
+                DfsEvent::Discover(v) => {
+                    time += 1;
+                    self.vertex_list[v].borrow_mut().discover_time = time;
+                }
+                DfsEvent::Finish(v) => {
+                    time += 1;
+                    let mut v = self.vertex_list[v].borrow_mut();
+                    v.finish_time = time;
+                    v.color = Color::Black;
+                }
+                DfsEvent::Edge(u, v, EdgeKind::Tree) => {
+                    self.vertex_list[v].borrow_mut().ancestor = Some(self.get_vertex(u));
+                }
+                DfsEvent::Edge(..) => {}
+            }
+        }
+    }
+
+    pub fn topological_sort(&mut self) -> Vec<(usize, u32)> {
+        self.depth_first_search();
+
+        let mut order = Vec::new();
+        for v in self.iter() {
+            order.push((v.borrow().idx, v.borrow().finish_time));
+        }
+
+        // order.sort_by(|a, b| a.1.partial_cmp(b.1));
+
+        order
+    }
+
+    pub fn get_edges_heap_min(&self) -> BinaryHeap<&Edge<W>> {
+        let mut heap = BinaryHeap::new();
+        for e in self.iter_edges() {
+            heap.push(e);
+        }
+        heap
+    }
+
+    // pub fn get_edges_heap_min(&self) -> BinaryHeap<Reverse<&Edge>> {
+    //     let mut heap = BinaryHeap::new();
+    //     for e in self.iter_edges() {
+    //         heap.push(Reverse(e));
+    //     }
+    //     heap
+    // }
+}
+
+// DFS events on an undirected graph: every edge is either a tree edge or a
+// back edge, and is reported once.
+impl<W: Weight> UndiGraph<W> {
+    pub fn dfs_events(&self) -> DfsEvents {
+        search::dfs_events(self)
+    }
+
+    pub fn dfs_events_from(&self, start: usize) -> DfsEvents {
+        search::dfs_events_from(self, start)
+    }
+
+    // Tree edge (u, v) is a bridge when nothing below v has a back edge
+    // reaching u or higher. Parallel edges are never bridges.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        let n = self.V();
+        let mut discover = vec![0; n];
+        let mut low = vec![0; n];
+        let mut parent = vec![None; n];
+        let mut time = 0;
+        let mut res = vec![];
+
+        for event in self.dfs_events() {
+            match event {
+                DfsEvent::Discover(v) => {
+                    time += 1;
+                    discover[v] = time;
+                    low[v] = time;
+                }
+                DfsEvent::Edge(u, v, EdgeKind::Tree) => parent[v] = Some(u),
+                DfsEvent::Edge(u, v, _) => low[u] = low[u].min(discover[v]),
+                DfsEvent::Finish(v) => {
+                    if let Some(p) = parent[v] {
+                        low[p] = low[p].min(low[v]);
+                        if low[v] > discover[p] {
+                            res.push((p, v));
+                        }
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+// Derived graphs, see `subgraph`.
+impl<W: Weight> UndiGraph<W> {
+    pub fn induced_subgraph(&self, vertices: &[usize]) -> InducedSubgraph<'_, Self> {
+        InducedSubgraph::new(self, vertices)
+    }
+
+    pub fn filtered<P>(&self, keep: P) -> FilteredGraph<'_, Self, P>
+    where
+        P: Fn(usize, usize, W) -> bool,
+    {
+        FilteredGraph::new(self, keep)
+    }
+
+    // The graph with the `down` edges taken out, e.g. for "what if these
+    // links fail" questions.
+    pub fn without_edges(
+        &self,
+        down: &[(usize, usize)],
+    ) -> FilteredGraph<'_, Self, impl Fn(usize, usize, W) -> bool> {
+        let down: HashSet<(usize, usize)> =
+            down.iter().map(|&(u, v)| (u.min(v), u.max(v))).collect();
+        FilteredGraph::new(self, move |u, v, _| !down.contains(&(u, v)))
+    }
+
+    pub fn complement(&self) -> Self {
+        subgraph::complement(self, UndiGraph::new(0))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        subgraph::union(self, other, UndiGraph::new(0))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        subgraph::intersection(self, other, UndiGraph::new(0))
+    }
+
+    // Also returns the edge each vertex of the line graph stands for.
+    pub fn line_graph(&self) -> (Self, Vec<(usize, usize)>) {
+        subgraph::line_graph(self, UndiGraph::new(0))
+    }
+
+    pub fn cartesian_product(&self, other: &Self) -> Self {
+        subgraph::cartesian_product(self, other, UndiGraph::new(0))
+    }
+}
+
+// Connected component labelling. Components are numbered in order of their
+// smallest vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Components {
+    label: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    fn from_labels(raw: Vec<usize>) -> Self {
+        let mut renumber = vec![usize::MAX; raw.len()];
+        let mut label = Vec::with_capacity(raw.len());
+        let mut sizes = Vec::new();
+        for r in raw {
+            if renumber[r] == usize::MAX {
+                renumber[r] = sizes.len();
+                sizes.push(0);
+            }
+            label.push(renumber[r]);
+            sizes[renumber[r]] += 1;
+        }
+        Components { label, sizes }
+    }
+
+    fn from_set(set: &DSForest, v: usize) -> Self {
+        Components::from_labels((0..v).map(|x| set.find_set(x).borrow().name()).collect())
+    }
+
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn label(&self, v: usize) -> usize {
+        self.label[v]
+    }
+
+    pub fn labels(&self) -> &[usize] {
+        &self.label
+    }
+
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    pub fn members(&self, c: usize) -> Vec<usize> {
+        (0..self.label.len())
+            .filter(|&v| self.label[v] == c)
+            .collect()
+    }
+
+    // The biggest component; ties go to the lower label.
+    pub fn largest(&self) -> Option<usize> {
+        (0..self.count()).max_by_key(|&c| (self.sizes[c], std::cmp::Reverse(c)))
+    }
+}
+
+// Connected components
+impl<W: Weight> UndiGraph<W> {
+    pub fn connected_components_bfs(&self) -> Components {
+        let mut raw = vec![usize::MAX; self.V()];
+        let mut que = std::collections::VecDeque::new();
+        for root in 0..self.V() {
+            if raw[root] != usize::MAX {
+                continue;
+            }
+            raw[root] = root;
+            que.push_back(root);
+            while let Some(x) = que.pop_front() {
+                for (_, u) in self.get_vertex(x).borrow().iter() {
+                    let u_idx = u.borrow().idx;
+                    if raw[u_idx] == usize::MAX {
+                        raw[u_idx] = root;
+                        que.push_back(u_idx);
+                    }
+                }
+            }
+        }
+        Components::from_labels(raw)
+    }
+
+    pub fn connected_components_ds(&self) -> Components {
+        match self.components.as_ref() {
+            Some(set) => Components::from_set(set, self.V()),
+            None => Components::from_set(&self.build_component_set(), self.V()),
+        }
+    }
+
+    fn build_component_set(&self) -> DSForest {
+        let mut set = DSForest::new(self.V());
+        for e in self.iter_edges() {
+            if !set.is_linked(e.v1, e.v2) {
+                set.union(e.v1, e.v2);
+            }
+        }
+        set
+    }
+
+    // Keeps a disjoint-set forest in step with `add_edge` and `add_vertex`,
+    // so `same_component` and `component_count` answer in O(α(V)).
+    pub fn enable_incremental_components(&mut self) {
+        if self.components.is_none() {
+            self.components = Some(self.build_component_set());
+        }
+    }
+
+    pub fn disable_incremental_components(&mut self) {
+        self.components = None;
+    }
+
+    // Falls back to a BFS from `u` when incremental components are off.
+    pub fn same_component(&self, u: usize, v: usize) -> bool {
+        match self.components.as_ref() {
+            Some(set) => set.is_linked(u, v),
+            None => {
+                let mut seen = vec![false; self.V()];
+                let mut stack = vec![u];
+                seen[u] = true;
+                while let Some(x) = stack.pop() {
+                    if x == v {
+                        return true;
+                    }
+                    for (_, y) in self.get_vertex(x).borrow().iter() {
+                        let y_idx = y.borrow().idx;
+                        if !seen[y_idx] {
+                            seen[y_idx] = true;
+                            stack.push(y_idx);
+                        }
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    pub fn component_count(&self) -> usize {
+        match self.components.as_ref() {
+            Some(set) => set.forests(),
+            None => self.connected_components_bfs().count(),
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.component_count() <= 1
+    }
+
+    // The induced subgraph on the biggest component, along with the original
+    // index of each of its vertices.
+    pub fn largest_component_subgraph(&self) -> (UndiGraph<W>, Vec<usize>) {
+        let components = self.connected_components_bfs();
+        let members = components
+            .largest()
+            .map_or(Vec::new(), |c| components.members(c));
+
+        let mut new_idx = vec![usize::MAX; self.V()];
+        for (i, &v) in members.iter().enumerate() {
+            new_idx[v] = i;
+        }
+        let mut g = UndiGraph::new(members.len());
+        for e in self.iter_edges() {
+            if new_idx[e.v1] != usize::MAX {
+                g.add_edge(new_idx[e.v2], new_idx[e.v1], e.weight);
+            }
+        }
+        (g, members)
+    }
+}
+
+// Minimum spinning trees
+impl<W: Weight> UndiGraph<W> {
+    pub fn kerskal_mst(&self) -> Vec<Edge<W>> {
+        let mut heap = self.get_edges_heap_min();
+        let mut set = DSForest::new(self.V());
+        let mut mst = Vec::new();
+
+        while let Some(e) = heap.pop() {
+            let Edge { v1, v2, weight: _ } = e.clone();
+            if !set.is_linked(v1, v2) {
+                set.union(v1, v2);
+                mst.push(e.clone());
+            }
+
+            if set.forests() == 1 {
+                break;
+            }
+        }
+
+        mst
+    }
+
+    // Tree of the component that holds vertex 0.
+    pub fn prim_mst(&self) -> Vec<Edge<W>> {
+        if self.V() == 0 {
+            return Vec::new();
+        }
+        let mut visited = vec![false; self.V()];
+        let mut heap = IndexedBinaryHeap::with_capacity(self.V());
+        self.prim_tree_with(0, &mut visited, &mut heap)
+    }
+
+    // Prim with decrease-key: each vertex sits in the queue at most once,
+    // keyed by the lightest edge that links it to the tree so far.
+    fn prim_tree_with<H>(&self, root: usize, visited: &mut [bool], heap: &mut H) -> Vec<Edge<W>>
+    where
+        H: AddressableHeap<OrdWeight<W>>,
+    {
+        let mut mst = Vec::new();
+        let mut link: Vec<Option<(usize, W)>> = vec![None; self.V()];
+
+        heap.push(root, OrdWeight(W::zero()));
+        while let Some((u_idx, _)) = heap.pop() {
+            visited[u_idx] = true;
+            if let Some((from, weight)) = link[u_idx] {
+                mst.push(Edge::new(from, u_idx, weight));
+            }
+
+            let u = self.get_vertex(u_idx);
+            for (weight, v) in u.borrow().iter() {
+                let v_idx = v.borrow().idx;
+                if visited[v_idx] {
+                    continue;
+                }
+                if let Some((_, best)) = link[v_idx] {
+                    if weight.cmp_weight(&best) != std::cmp::Ordering::Less {
+                        continue;
+                    }
+                }
+                link[v_idx] = Some((u_idx, *weight));
+                if !heap.decrease_key(v_idx, OrdWeight(*weight)) {
+                    heap.push(v_idx, OrdWeight(*weight));
+                }
+            }
+        }
+
+        mst
+    }
+}
+
+// A minimum spanning forest: one tree per connected component, isolated
+// vertices included as empty trees.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<W = f32> {
+    trees: Vec<Vec<Edge<W>>>,
+    weight: W,
+}
+
+impl<W: Weight> SpanningForest<W> {
+    fn new(trees: Vec<Vec<Edge<W>>>) -> Self {
+        let weight = trees
+            .iter()
+            .flatten()
+            .fold(W::zero(), |acc, e| acc.saturating_plus(e.weight));
+        SpanningForest { trees, weight }
+    }
+
+    // Groups a bag of forest edges by the component they span.
+    fn from_edges(v: usize, edges: Vec<Edge<W>>) -> Self {
+        let mut set = DSForest::new(v);
+        for e in edges.iter() {
+            if !set.is_linked(e.v1, e.v2) {
+                set.union(e.v1, e.v2);
+            }
+        }
+
+        let mut tree_of = vec![usize::MAX; v];
+        let mut trees = Vec::new();
+        for x in 0..v {
+            let root = set.find_set(x).borrow().name();
+            if tree_of[root] == usize::MAX {
+                tree_of[root] = trees.len();
+                trees.push(Vec::new());
+            }
+        }
+        for e in edges {
+            let root = set.find_set(e.v1).borrow().name();
+            trees[tree_of[root]].push(e);
+        }
+
+        SpanningForest::new(trees)
+    }
+
+    pub fn trees(&self) -> &[Vec<Edge<W>>] {
+        &self.trees
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.trees.iter().flatten()
+    }
+
+    pub fn total_weight(&self) -> W {
+        self.weight
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.trees.len()
+    }
+}
+
+// Minimum spanning forests, for graphs that may be disconnected
+impl<W: Weight> UndiGraph<W> {
+    pub fn kerskal_msf(&self) -> SpanningForest<W> {
+        SpanningForest::from_edges(self.V(), self.kerskal_mst())
+    }
+
+    pub fn prim_msf(&self) -> SpanningForest<W> {
+        self.prim_msf_with::<IndexedBinaryHeap<_>>()
+    }
+
+    // Prim over any addressable heap, e.g. `FibonacciHeap<_>` for the
+    // O(E + V log V) bound of CLRS 23.2.
+    pub fn prim_msf_with<H>(&self) -> SpanningForest<W>
+    where
+        H: AddressableHeap<OrdWeight<W>>,
+    {
+        let mut visited = vec![false; self.V()];
+        let mut heap = H::with_capacity(self.V());
+        let mut trees = Vec::new();
+        for root in 0..self.V() {
+            if !visited[root] {
+                trees.push(self.prim_tree_with(root, &mut visited, &mut heap));
+            }
+        }
+        SpanningForest::new(trees)
+    }
+
+    // Every round each component picks its lightest outgoing edge, so there
+    // are at most log V rounds over the edge list.
+    pub fn boruvka_msf(&self) -> SpanningForest<W> {
+        let mut set = DSForest::new(self.V());
+        let mut forest = Vec::new();
+
+        loop {
+            let mut cheapest: Vec<Option<&Edge<W>>> = vec![None; self.V()];
+            for e in self.iter_edges() {
+                let c1 = set.find_set(e.v1).borrow().name();
+                let c2 = set.find_set(e.v2).borrow().name();
+                if c1 == c2 {
+                    continue;
+                }
+                for c in [c1, c2] {
+                    // `Edge` orders heavier edges first.
+                    if cheapest[c].is_none_or(|best| e > best) {
+                        cheapest[c] = Some(e);
+                    }
+                }
+            }
+
+            let mut merged = false;
+            for e in cheapest.into_iter().flatten() {
+                if !set.is_linked(e.v1, e.v2) {
+                    set.union(e.v1, e.v2);
+                    forest.push(e.clone());
+                    merged = true;
+                }
+            }
+            if !merged {
+                break;
+            }
+        }
+
+        SpanningForest::from_edges(self.V(), forest)
+    }
+
+    // True when `edges` is a minimum spanning forest of this graph: every edge
+    // is a graph edge, they span each component without a cycle, and no
+    // other edge is lighter than the heaviest tree edge on the path it closes.
+    pub fn verify_msf(&self, edges: &[Edge<W>]) -> bool {
+        // graph edges by their endpoints, each matched at most once
+        let graph_edges: Vec<&Edge<W>> = self.iter_edges().collect();
+        let mut by_ends: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, e) in graph_edges.iter().enumerate() {
+            by_ends
+                .entry((e.v1.min(e.v2), e.v1.max(e.v2)))
+                .or_default()
+                .push(i);
+        }
+        let mut used = vec![false; graph_edges.len()];
+        for e in edges {
+            let candidates = by_ends.get(&(e.v1.min(e.v2), e.v1.max(e.v2)));
+            let matched = candidates.and_then(|c| {
+                c.iter().copied().find(|&i| {
+                    !used[i]
+                        && graph_edges[i].weight.cmp_weight(&e.weight) == std::cmp::Ordering::Equal
+                })
+            });
+            match matched {
+                Some(i) => used[i] = true,
+                None => return false,
+            }
+        }
+        let unused: Vec<&Edge<W>> = graph_edges
+            .iter()
+            .zip(used)
+            .filter(|&(_, used)| !used)
+            .map(|(e, _)| *e)
+            .collect();
+
+        let mut set = DSForest::new(self.V());
+        for e in edges {
+            if set.is_linked(e.v1, e.v2) {
+                return false;
+            }
+            set.union(e.v1, e.v2);
+        }
+        if unused.iter().any(|e| !set.is_linked(e.v1, e.v2)) {
+            return false;
+        }
+
+        let path_max = PathMax::new(self.V(), edges);
+        unused.iter().all(|e| match path_max.query(e.v1, e.v2) {
+            Some((heaviest, _)) => heaviest.cmp_weight(&e.weight) != std::cmp::Ordering::Greater,
+            None => true,
+        })
+    }
+}
+
+// How far an edge's weight can move before the minimum spanning forest
+// changes: a tree edge may rise by `slack`, a non-tree edge may fall by it.
+// Pushing past the slack swaps the edge out of (or into) the forest.
+#[derive(Debug, Clone)]
+pub struct EdgeSensitivity<W = f32> {
+    edge: Edge<W>,
+    in_tree: bool,
+    slack: W,
+}
+
+impl<W: Weight> EdgeSensitivity<W> {
+    pub fn edge(&self) -> &Edge<W> {
+        &self.edge
+    }
+
+    pub fn in_tree(&self) -> bool {
+        self.in_tree
+    }
+
+    // Infinite for bridges and for self-loops.
+    pub fn slack(&self) -> W {
+        self.slack
+    }
+}
+
+// Second-best spanning tree and sensitivity analysis
+impl<W: Weight> UndiGraph<W> {
+    // Marks the edges, in `iter_edges` order, that Kruskal puts in the forest.
+    fn msf_edge_flags(&self) -> Vec<bool> {
+        let edges: Vec<&Edge<W>> = self.iter_edges().collect();
+        let mut order: Vec<usize> = (0..edges.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(edges[i]));
+
+        let mut set = DSForest::new(self.V());
+        let mut in_tree = vec![false; edges.len()];
+        for i in order {
+            let Edge { v1, v2, weight: _ } = *edges[i];
+            if !set.is_linked(v1, v2) {
+                set.union(v1, v2);
+                in_tree[i] = true;
+            }
+        }
+        in_tree
+    }
+
+    // CLRS problem 23-1: the cheapest spanning forest other than the minimum
+    // one, found by swapping a single non-tree edge for the heaviest tree
+    // edge on the cycle it closes. None if every edge is in the forest.
+    pub fn second_best_mst(&self) -> Option<SpanningForest<W>> {
+        let edges: Vec<Edge<W>> = self.iter_edges().cloned().collect();
+        let in_tree = self.msf_edge_flags();
+        let (tree, others): (Vec<usize>, Vec<usize>) = (0..edges.len()).partition(|&i| in_tree[i]);
+        let mut forest: Vec<Edge<W>> = tree.iter().map(|&i| edges[i].clone()).collect();
+        let path_max = PathMax::new(self.V(), &forest);
+
+        let mut best: Option<(W, usize, usize)> = None;
+        for i in others {
+            let Edge { v1, v2, weight } = edges[i];
+            if let Some((heaviest, k)) = path_max.query(v1, v2) {
+                let extra = weight.saturating_minus(heaviest);
+                if best.is_none_or(|(b, _, _)| extra.cmp_weight(&b) == std::cmp::Ordering::Less) {
+                    best = Some((extra, i, k));
+                }
+            }
+        }
+
+        let (_, i, k) = best?;
+        forest[k] = edges[i].clone();
+        Some(SpanningForest::from_edges(self.V(), forest))
+    }
+
+    // One entry per graph edge, in `iter_edges` order.
+    pub fn mst_sensitivity(&self) -> Vec<EdgeSensitivity<W>> {
+        let edges: Vec<Edge<W>> = self.iter_edges().cloned().collect();
+        let in_tree = self.msf_edge_flags();
+        let tree: Vec<usize> = (0..edges.len()).filter(|&i| in_tree[i]).collect();
+        let forest: Vec<Edge<W>> = tree.iter().map(|&i| edges[i].clone()).collect();
+        let path_max = PathMax::new(self.V(), &forest);
+
+        // The lightest non-tree edge covering each tree edge is its replacement.
+        // Non-tree edges are taken lightest first, and `jump` skips tree edges
+        // that already have one, so every tree edge is assigned once.
+        let mut others: Vec<usize> = (0..edges.len()).filter(|&i| !in_tree[i]).collect();
+        others.sort_by_key(|&i| std::cmp::Reverse(&edges[i]));
+        let mut replacement: Vec<Option<W>> = vec![None; forest.len()];
+        let mut jump: Vec<usize> = (0..self.V()).collect();
+        fn find(jump: &mut [usize], mut x: usize) -> usize {
+            let mut root = x;
+            while jump[root] != root {
+                root = jump[root];
+            }
+            while jump[x] != root {
+                let next = jump[x];
+                jump[x] = root;
+                x = next;
+            }
+            root
+        }
+        for &i in others.iter() {
+            let Edge { v1, v2, weight } = edges[i];
+            if path_max.query(v1, v2).is_none() {
+                continue;
+            }
+            let top = path_max.depth[path_max.lca(v1, v2)];
+            for side in [v1, v2] {
+                let mut x = find(&mut jump, side);
+                while path_max.depth[x] > top {
+                    replacement[path_max.parent_edge(x).unwrap()] = Some(weight);
+                    jump[x] = path_max.parent(x);
+                    x = find(&mut jump, x);
+                }
+            }
+        }
+
+        let mut res = Vec::with_capacity(edges.len());
+        let mut k = 0;
+        for (i, edge) in edges.into_iter().enumerate() {
+            let slack = if in_tree[i] {
+                k += 1;
+                replacement[k - 1].map_or(W::infinity(), |r| r.saturating_minus(edge.weight))
+            } else {
+                path_max
+                    .query(edge.v1, edge.v2)
+                    .map_or(W::infinity(), |(heaviest, _)| {
+                        edge.weight.saturating_minus(heaviest)
+                    })
+            };
+            res.push(EdgeSensitivity {
+                edge,
+                in_tree: in_tree[i],
+                slack,
+            });
+        }
+        res
+    }
+}
+
+// Heaviest edge on the forest path between two vertices, answered in
+// O(log V) with binary lifting. Edges are reported by their position in the
+// slice the forest was built from.
+struct PathMax<W> {
+    depth: Vec<usize>,
+    tree: Vec<usize>,
+    up: Vec<Vec<usize>>,
+    heaviest: Vec<Vec<Option<(W, usize)>>>,
+}
+
+fn heavier<W: Weight>(a: Option<(W, usize)>, b: Option<(W, usize)>) -> Option<(W, usize)> {
+    match (a, b) {
+        (Some(x), Some(y)) if x.0.cmp_weight(&y.0) == std::cmp::Ordering::Less => Some(y),
+        (Some(x), _) => Some(x),
+        (None, y) => y,
+    }
+}
+
+impl<W: Weight> PathMax<W> {
+    fn new(v: usize, edges: &[Edge<W>]) -> Self {
+        let mut adj = vec![vec![]; v];
+        for (i, e) in edges.iter().enumerate() {
+            adj[e.v1].push((e.v2, i));
+            adj[e.v2].push((e.v1, i));
+        }
+
+        let mut levels = 1;
+        while (1 << levels) < v {
+            levels += 1;
+        }
+        let mut depth = vec![0; v];
+        let mut tree = vec![usize::MAX; v];
+        let mut up = vec![(0..v).collect::<Vec<_>>(); levels];
+        let mut heaviest = vec![vec![None; v]; levels];
+
+        for root in 0..v {
+            if tree[root] != usize::MAX {
+                continue;
+            }
+            tree[root] = root;
+            let mut stack = vec![root];
+            while let Some(x) = stack.pop() {
+                for &(y, i) in adj[x].iter() {
+                    if tree[y] == usize::MAX {
+                        tree[y] = root;
+                        depth[y] = depth[x] + 1;
+                        up[0][y] = x;
+                        heaviest[0][y] = Some((edges[i].weight, i));
+                        stack.push(y);
+                    }
+                }
+            }
+        }
+
+        for j in 1..levels {
+            for x in 0..v {
+                let mid = up[j - 1][x];
+                up[j][x] = up[j - 1][mid];
+                heaviest[j][x] = heavier(heaviest[j - 1][x], heaviest[j - 1][mid]);
+            }
+        }
+
+        PathMax {
+            depth,
+            tree,
+            up,
+            heaviest,
+        }
+    }
+
+    fn parent(&self, x: usize) -> usize {
+        self.up[0][x]
+    }
+
+    // The forest edge from `x` to its parent; None for roots.
+    fn parent_edge(&self, x: usize) -> Option<usize> {
+        self.heaviest[0][x].map(|(_, i)| i)
+    }
+
+    // None when the vertices are equal or in different trees.
+    fn query(&self, mut a: usize, mut b: usize) -> Option<(W, usize)> {
+        if self.tree[a] != self.tree[b] {
+            return None;
+        }
+
+        let mut best = None;
+        if self.depth[a] < self.depth[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let diff = self.depth[a] - self.depth[b];
+        for j in 0..self.up.len() {
+            if diff >> j & 1 == 1 {
+                best = heavier(best, self.heaviest[j][a]);
+                a = self.up[j][a];
+            }
+        }
+        if a != b {
+            for j in (0..self.up.len()).rev() {
+                if self.up[j][a] != self.up[j][b] {
+                    best = heavier(best, self.heaviest[j][a]);
+                    best = heavier(best, self.heaviest[j][b]);
+                    a = self.up[j][a];
+                    b = self.up[j][b];
+                }
+            }
+            best = heavier(best, self.heaviest[0][a]);
+            best = heavier(best, self.heaviest[0][b]);
+        }
+
+        best
+    }
+
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        if self.depth[a] < self.depth[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let diff = self.depth[a] - self.depth[b];
+        for j in 0..self.up.len() {
+            if diff >> j & 1 == 1 {
+                a = self.up[j][a];
+            }
+        }
+        if a == b {
+            return a;
+        }
+        for j in (0..self.up.len()).rev() {
+            if self.up[j][a] != self.up[j][b] {
+                a = self.up[j][a];
+                b = self.up[j][b];
+            }
+        }
+        self.up[0][a]
+    }
+}
+
+pub fn parse_graph_from_stdio_by_edges<W>() -> Result<UndiGraph<W>, std::io::Error>
+where
+    W: Weight + std::str::FromStr,
+{
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+
+    // println!("parse_graph_from_stdio:  {}", input);
+    let v = input.trim().parse::<usize>().unwrap();
+
+    input.clear();
+    std::io::stdin().read_line(&mut input)?;
+    let e = input.trim().parse::<usize>().unwrap();
+
+    let mut g = UndiGraph::new(v);
+    for _ in 0..e {
+        input.clear();
+        std::io::stdin().read_line(&mut input)?;
+        let nums: Vec<&str> = input.split_ascii_whitespace().collect();
+        let weight = nums[2].trim().parse::<W>().ok().filter(|w| w.is_valid());
+        let weight = weight.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid weight `{}`", nums[2]),
+            )
+        })?;
+        g.add_edge(
+            nums[0].trim().parse::<usize>().unwrap(),
+            nums[1].trim().parse::<usize>().unwrap(),
+            weight,
+        );
+    }
+
+    Ok(g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_components() -> UndiGraph<i32> {
+        let edges = vec![
+            (0, 1, 4),
+            (1, 2, 2),
+            (0, 2, 3),
+            (2, 3, 5),
+            (1, 3, 5),
+            (4, 5, 1),
+            (5, 6, 1),
+            (4, 6, 1),
+        ];
+        UndiGraph::build_graph(8, edges)
+    }
+
+    #[test]
+    fn spanning_forests_agree() {
+        let g = two_components();
+        let fib = g.prim_msf_with::<fibonacii_heap::FibonacciHeap<_>>();
+        for forest in [g.kerskal_msf(), g.prim_msf(), g.boruvka_msf(), fib] {
+            assert_eq!(forest.total_weight(), 12);
+            assert_eq!(forest.component_count(), 3);
+            assert_eq!(forest.edges().count(), 5);
+            let edges: Vec<Edge<i32>> = forest.edges().cloned().collect();
+            assert!(g.verify_msf(&edges));
+        }
+        assert_eq!(g.prim_mst().len(), 3);
+    }
+
+    #[test]
+    fn second_best_and_sensitivity() {
+        let g = two_components();
+        let second = g.second_best_mst().unwrap();
+        assert_eq!(second.total_weight(), 12);
+
+        let chain = UndiGraph::build_graph(
+            4,
+            vec![(0, 1, 1), (1, 2, 2), (2, 3, 3), (0, 3, 7), (0, 2, 4)],
+        );
+        let second = chain.second_best_mst().unwrap();
+        assert_eq!(second.total_weight(), 8);
+        let edges: Vec<Edge<i32>> = second.edges().cloned().collect();
+        assert!(!chain.verify_msf(&edges));
+
+        let slack: Vec<(bool, i32)> = chain
+            .mst_sensitivity()
+            .iter()
+            .map(|s| (s.in_tree(), s.slack()))
+            .collect();
+        assert_eq!(
+            slack,
+            vec![(true, 3), (true, 2), (true, 4), (false, 4), (false, 2)]
+        );
+
+        let tree = UndiGraph::build_graph(3, vec![(0, 1, 1), (1, 2, 2)]);
+        assert!(tree.second_best_mst().is_none());
+        assert!(tree.mst_sensitivity().iter().all(|s| s.slack() == i32::MAX));
+    }
+
+    #[test]
+    fn connected_components() {
+        let mut g = two_components();
+        let bfs = g.connected_components_bfs();
+        assert_eq!(bfs, g.connected_components_ds());
+        assert_eq!(bfs.sizes(), &[4, 3, 1]);
+        assert_eq!(bfs.label(6), 1);
+        assert_eq!(bfs.largest(), Some(0));
+
+        let (sub, members) = g.largest_component_subgraph();
+        assert_eq!(members, vec![0, 1, 2, 3]);
+        assert_eq!((sub.V(), sub.E()), (4, 5));
+
+        g.enable_incremental_components();
+        assert!(!g.same_component(0, 4));
+        assert_eq!(g.component_count(), 3);
+        g.add_edge(3, 6, 1);
+        g.add_vertex(Vertex::new_vptr(8));
+        assert!(g.same_component(0, 4));
+        assert_eq!(g.component_count(), 3);
+        g.disable_incremental_components();
+        assert!(g.same_component(0, 4) && !g.same_component(0, 8));
+    }
+
+    #[test]
+    fn verify_rejects_non_minimal_forest() {
+        let g = two_components();
+        let heavy = vec![
+            Edge::new(0, 1, 4),
+            Edge::new(1, 2, 2),
+            Edge::new(2, 3, 5),
+            Edge::new(4, 5, 1),
+            Edge::new(5, 6, 1),
+        ];
+        assert!(!g.verify_msf(&heavy));
+        assert!(!g.verify_msf(&heavy[1..]));
+        let bogus = vec![Edge::new(0, 3, 1)];
+        assert!(!g.verify_msf(&bogus));
+    }
+}
@@ -0,0 +1,418 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Edge weights and path lengths.
+// `infinity()` is the "unreachable" distance: adding anything to it stays
+// infinite, so relaxing an edge out of an unreached vertex never succeeds.
+pub trait Weight:
+    Copy + PartialEq + PartialOrd + fmt::Debug + fmt::Display + Send + Sync + 'static
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn infinity() -> Self;
+
+    fn is_infinite(self) -> bool {
+        self == Self::infinity()
+    }
+
+    // False for values that can't take part in a comparison, i.e. float NaN.
+    fn is_valid(self) -> bool {
+        true
+    }
+
+    // None on overflow (or inf + -inf for floats). For integers a finite
+    // sum that lands on the `MAX` sentinel overflows too.
+    fn checked_plus(self, rhs: Self) -> Option<Self>;
+
+    // Clamps an overflow to infinity (or to the most negative value).
+    fn saturating_plus(self, rhs: Self) -> Self;
+
+    // Infinity minus a finite weight stays infinite; a finite weight minus
+    // infinity is the most negative value.
+    fn saturating_minus(self, rhs: Self) -> Self;
+
+    fn cmp_weight(&self, other: &Self) -> Ordering;
+
+    fn to_f64(self) -> f64;
+
+    fn min_weight(self, other: Self) -> Self {
+        match self.cmp_weight(&other) {
+            Ordering::Greater => other,
+            _ => self,
+        }
+    }
+}
+
+// Totally ordered wrapper, for containers such as the addressable heaps
+// that need `Ord` keys.
+#[derive(Copy, Clone, Debug)]
+pub struct OrdWeight<W>(pub W);
+
+impl<W: Weight> PartialEq for OrdWeight<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for OrdWeight<W> {}
+
+impl<W: Weight> PartialOrd for OrdWeight<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for OrdWeight<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_weight(&other.0)
+    }
+}
+
+macro_rules! impl_weight_for_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn infinity() -> Self {
+                    <$t>::MAX
+                }
+
+                fn checked_plus(self, rhs: Self) -> Option<Self> {
+                    if self.is_infinite() || rhs.is_infinite() {
+                        return Some(Self::infinity());
+                    }
+                    <$t>::checked_add(self, rhs).filter(|sum| !sum.is_infinite())
+                }
+
+                fn saturating_plus(self, rhs: Self) -> Self {
+                    if self.is_infinite() || rhs.is_infinite() {
+                        return Self::infinity();
+                    }
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                fn saturating_minus(self, rhs: Self) -> Self {
+                    if self.is_infinite() {
+                        return Self::infinity();
+                    }
+                    if rhs.is_infinite() {
+                        return <$t>::MIN;
+                    }
+                    <$t>::saturating_sub(self, rhs)
+                }
+
+                fn cmp_weight(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_weight_for_float {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn infinity() -> Self {
+                    <$t>::INFINITY
+                }
+
+                fn is_valid(self) -> bool {
+                    !self.is_nan()
+                }
+
+                fn checked_plus(self, rhs: Self) -> Option<Self> {
+                    let sum = self + rhs;
+                    if sum.is_nan() {
+                        None
+                    } else {
+                        Some(sum)
+                    }
+                }
+
+                // `Weight::is_infinite`, since the inherent one is true for
+                // -inf as well
+                fn saturating_plus(self, rhs: Self) -> Self {
+                    if Weight::is_infinite(self) || Weight::is_infinite(rhs) {
+                        return Self::infinity();
+                    }
+                    self + rhs
+                }
+
+                fn saturating_minus(self, rhs: Self) -> Self {
+                    if Weight::is_infinite(self) {
+                        return Self::infinity();
+                    }
+                    self - rhs
+                }
+
+                fn cmp_weight(&self, other: &Self) -> Ordering {
+                    self.total_cmp(other)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_for_float!(f32, f64);
+
+// Exact rational weight, always kept in lowest terms with a positive
+// denominator. 1/0 stands for infinity; there is no 0/0 or -1/0.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i64,
+    den: i64,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a.abs()
+}
+
+impl Ratio {
+    // Panics on 0/0 and on a negative number over 0.
+    pub fn new(num: i64, den: i64) -> Ratio {
+        assert!(den != 0 || num > 0, "{}/{} is not a Ratio", num, den);
+        Ratio::reduce(num as i128, den as i128).expect("Ratio doesn't fit in i64")
+    }
+
+    pub fn from_integer(n: i64) -> Ratio {
+        Ratio { num: n, den: 1 }
+    }
+
+    pub fn numer(&self) -> i64 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i64 {
+        self.den
+    }
+
+    fn reduce(mut num: i128, mut den: i128) -> Option<Ratio> {
+        if den == 0 {
+            return Some(Ratio { num: 1, den: 0 });
+        }
+        if den < 0 {
+            num = -num;
+            den = -den;
+        }
+        let g = gcd(num, den).max(1);
+        let (num, den) = (num / g, den / g);
+        if num > i64::MAX as i128 || num < i64::MIN as i128 || den > i64::MAX as i128 {
+            return None;
+        }
+        Some(Ratio {
+            num: num as i64,
+            den: den as i64,
+        })
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.den == 0, other.den == 0) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 0 {
+            write!(f, "INF")
+        } else if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRatioError {
+    Int(std::num::ParseIntError),
+    // 0/0, or a negative number over 0
+    ZeroDenominator,
+}
+
+impl From<std::num::ParseIntError> for ParseRatioError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        ParseRatioError::Int(e)
+    }
+}
+
+impl fmt::Display for ParseRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRatioError::Int(e) => write!(f, "{}", e),
+            ParseRatioError::ZeroDenominator => write!(f, "zero denominator"),
+        }
+    }
+}
+
+impl std::str::FromStr for Ratio {
+    type Err = ParseRatioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((num, den)) => {
+                let (num, den) = (num.trim().parse()?, den.trim().parse()?);
+                if den == 0 && num <= 0 {
+                    return Err(ParseRatioError::ZeroDenominator);
+                }
+                Ok(Ratio::new(num, den))
+            }
+            None => Ok(Ratio::from_integer(s.trim().parse()?)),
+        }
+    }
+}
+
+impl Weight for Ratio {
+    fn zero() -> Self {
+        Ratio::from_integer(0)
+    }
+
+    fn one() -> Self {
+        Ratio::from_integer(1)
+    }
+
+    fn infinity() -> Self {
+        Ratio { num: 1, den: 0 }
+    }
+
+    fn checked_plus(self, rhs: Self) -> Option<Self> {
+        if self.is_infinite() || rhs.is_infinite() {
+            return Some(Self::infinity());
+        }
+        let num = self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128;
+        Ratio::reduce(num, self.den as i128 * rhs.den as i128)
+    }
+
+    fn saturating_plus(self, rhs: Self) -> Self {
+        self.checked_plus(rhs).unwrap_or_else(|| {
+            if (self.num < 0) && (rhs.num < 0) {
+                Ratio::from_integer(i64::MIN)
+            } else {
+                Self::infinity()
+            }
+        })
+    }
+
+    fn saturating_minus(self, rhs: Self) -> Self {
+        if self.is_infinite() {
+            return Self::infinity();
+        }
+        if rhs.is_infinite() {
+            return Ratio::from_integer(i64::MIN);
+        }
+        let num = self.num as i128 * rhs.den as i128 - rhs.num as i128 * self.den as i128;
+        Ratio::reduce(num, self.den as i128 * rhs.den as i128).unwrap_or_else(|| {
+            if num < 0 {
+                Ratio::from_integer(i64::MIN)
+            } else {
+                Self::infinity()
+            }
+        })
+    }
+
+    fn cmp_weight(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn to_f64(self) -> f64 {
+        if self.den == 0 {
+            f64::INFINITY
+        } else {
+            self.num as f64 / self.den as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinity_absorbs_additions() {
+        assert!(i32::infinity().saturating_plus(-5).is_infinite());
+        assert_eq!(i32::MAX.checked_plus(1), Some(i32::MAX));
+        assert_eq!((i32::MAX - 2).checked_plus(1), Some(i32::MAX - 1));
+        // a finite sum can't become the unreachable sentinel
+        assert_eq!((i32::MAX - 1).checked_plus(1), None);
+        assert_eq!((i32::MAX - 1).checked_plus(2), None);
+        assert_eq!(5.saturating_minus(i32::infinity()), i32::MIN);
+        assert!(f32::infinity().saturating_plus(-1.0).is_infinite());
+        assert_eq!(f64::NEG_INFINITY.saturating_plus(1.0), f64::NEG_INFINITY);
+        assert_eq!(f64::NEG_INFINITY.saturating_minus(1.0), f64::NEG_INFINITY);
+        assert_eq!(1.0.saturating_minus(f64::infinity()), f64::NEG_INFINITY);
+        assert_eq!(f64::infinity().checked_plus(f64::NEG_INFINITY), None);
+        assert!(!f32::NAN.is_valid());
+    }
+
+    #[test]
+    fn ratio_arithmetic() {
+        let third = Ratio::new(2, 6);
+        assert_eq!(third, Ratio::new(-1, -3));
+        assert_eq!(third.checked_plus(Ratio::new(1, 6)), Some(Ratio::new(1, 2)));
+        assert!(third < Ratio::new(1, 2));
+        assert!(Ratio::infinity() > Ratio::from_integer(i64::MAX));
+        assert_eq!("3/4".parse::<Ratio>(), Ok(Ratio::new(3, 4)));
+        assert_eq!("2/0".parse::<Ratio>(), Ok(Ratio::infinity()));
+        assert_eq!(
+            "0/0".parse::<Ratio>(),
+            Err(ParseRatioError::ZeroDenominator)
+        );
+        assert_eq!(
+            "-1/0".parse::<Ratio>(),
+            Err(ParseRatioError::ZeroDenominator)
+        );
+        assert_eq!(
+            Ratio::one().saturating_minus(Ratio::infinity()),
+            Ratio::from_integer(i64::MIN)
+        );
+        assert_eq!(
+            Ratio::from_integer(i64::MAX).checked_plus(Ratio::one()),
+            None
+        );
+    }
+}
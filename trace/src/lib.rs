@@ -0,0 +1,339 @@
+// Execution traces for watching an algorithm run. Algorithms report what
+// they do -- an edge relaxation, a queue push, a B-tree split -- to a
+// `Trace` sink; `Recorder` keeps the events so a run can be written out as
+// JSON lines, stepped through in a viewer, or compared with another run.
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+macro_rules! impl_value_from {
+    ( $variant:ident, $as:ty, $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    Value::$variant(v as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_from!(Int, i64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+impl_value_from!(Float, f64, f32, f64);
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Str(v.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Str(v)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+impl Value {
+    fn write_json(&self, out: &mut String) {
+        match self {
+            Value::Null => out.push_str("null"),
+            Value::Bool(b) => write!(out, "{}", b).unwrap(),
+            Value::Int(i) => write!(out, "{}", i).unwrap(),
+            // JSON has no inf or NaN
+            Value::Float(f) if !f.is_finite() => out.push_str("null"),
+            Value::Float(f) => write!(out, "{}", f).unwrap(),
+            Value::Str(s) => write_json_str(s, out),
+        }
+    }
+}
+
+fn write_json_str(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// One step of an algorithm: what happened (`kind`, e.g. "relax") and named
+// fields describing it, kept in the order they were added.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    kind: &'static str,
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl Event {
+    pub fn new(kind: &'static str) -> Self {
+        Event {
+            kind,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn with<V: Into<Value>>(mut self, name: &'static str, value: V) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    pub fn fields(&self) -> &[(&'static str, Value)] {
+        &self.fields
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields.iter().find(|(n, _)| *n == name).map(|(_, v)| v)
+    }
+
+    // A single-line JSON object, e.g.
+    // {"step":4,"event":"relax","u":0,"v":2,"old":null,"new":7}
+    pub fn to_json(&self, step: usize) -> String {
+        let mut out = format!("{{\"step\":{},\"event\":", step);
+        write_json_str(self.kind, &mut out);
+        for (name, value) in self.fields.iter() {
+            out.push(',');
+            write_json_str(name, &mut out);
+            out.push(':');
+            value.write_json(&mut out);
+        }
+        out.push('}');
+        out
+    }
+}
+
+// Where algorithms send their events.
+pub trait Trace {
+    fn record(&mut self, event: Event);
+
+    // Sinks that drop everything return false, so the events aren't even
+    // built when nobody is watching.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn emit<F>(&mut self, event: F)
+    where
+        F: FnOnce() -> Event,
+        Self: Sized,
+    {
+        if self.is_enabled() {
+            self.record(event());
+        }
+    }
+}
+
+impl<T: Trace + ?Sized> Trace for &mut T {
+    fn record(&mut self, event: Event) {
+        (**self).record(event);
+    }
+
+    fn is_enabled(&self) -> bool {
+        (**self).is_enabled()
+    }
+}
+
+// The sink behind the untraced versions of the algorithms.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoTrace;
+
+impl Trace for NoTrace {
+    fn record(&mut self, _event: Event) {}
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+// Keeps every event in memory; the step of an event is its index.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorder {
+    events: Vec<Event>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder { events: Vec::new() }
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn into_events(self) -> Vec<Event> {
+        self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    // The events of one kind, e.g. every "relax".
+    pub fn of_kind<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Event> + 'a {
+        self.events.iter().filter(move |e| e.kind == kind)
+    }
+
+    pub fn to_json_lines(&self) -> String {
+        let mut out = String::new();
+        for (step, event) in self.events.iter().enumerate() {
+            out.push_str(&event.to_json(step));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn write_json_lines<W: Write>(&self, mut w: W) -> io::Result<()> {
+        for (step, event) in self.events.iter().enumerate() {
+            writeln!(w, "{}", event.to_json(step))?;
+        }
+        Ok(())
+    }
+
+    // The first step where two runs part ways, None if they are identical.
+    // A run that is a prefix of the other differs at its end.
+    pub fn first_difference(&self, other: &Recorder) -> Option<usize> {
+        let common = self.events.len().min(other.events.len());
+        (0..common)
+            .find(|&i| self.events[i] != other.events[i])
+            .or_else(|| Some(common).filter(|_| self.events.len() != other.events.len()))
+    }
+}
+
+impl Trace for Recorder {
+    fn record(&mut self, event: Event) {
+        self.events.push(event);
+    }
+}
+
+// Streams each event as a JSON line as soon as it is recorded, for runs
+// too long to keep in memory. Writing stops at the first I/O error, which
+// `finish` reports.
+#[derive(Debug)]
+pub struct JsonLines<W: Write> {
+    writer: W,
+    step: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLines<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLines {
+            writer,
+            step: 0,
+            error: None,
+        }
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Trace for JsonLines<W> {
+    fn record(&mut self, event: Event) {
+        if self.error.is_none() {
+            if let Err(e) = writeln!(self.writer, "{}", event.to_json(self.step)) {
+                self.error = Some(e);
+            }
+        }
+        self.step += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run<T: Trace>(mut trace: T, n: usize) -> T {
+        for i in 0..n {
+            trace.emit(|| Event::new("visit").with("v", i).with("odd", i % 2 == 1));
+        }
+        trace.emit(|| Event::new("done").with("note", "a \"quoted\"\nline"));
+        trace
+    }
+
+    #[test]
+    fn events_serialize_to_json_lines() {
+        let rec = run(Recorder::new(), 2);
+        assert_eq!(rec.len(), 3);
+        assert_eq!(rec.of_kind("visit").count(), 2);
+        assert_eq!(rec.events()[1].get("v"), Some(&Value::Int(1)));
+        assert_eq!(
+            rec.to_json_lines(),
+            "{\"step\":0,\"event\":\"visit\",\"v\":0,\"odd\":false}\n\
+             {\"step\":1,\"event\":\"visit\",\"v\":1,\"odd\":true}\n\
+             {\"step\":2,\"event\":\"done\",\"note\":\"a \\\"quoted\\\"\\nline\"}\n"
+        );
+
+        let e = Event::new("relax")
+            .with("old", None::<i32>)
+            .with("new", 2.5)
+            .with("inf", f64::INFINITY);
+        assert_eq!(
+            e.to_json(7),
+            "{\"step\":7,\"event\":\"relax\",\"old\":null,\"new\":2.5,\"inf\":null}"
+        );
+
+        let streamed = run(JsonLines::new(Vec::new()), 2).finish().unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), rec.to_json_lines());
+        let mut written = Vec::new();
+        rec.write_json_lines(&mut written).unwrap();
+        assert_eq!(written, rec.to_json_lines().into_bytes());
+    }
+
+    #[test]
+    fn runs_can_be_compared() {
+        let a = run(Recorder::new(), 3);
+        assert_eq!(a.first_difference(&run(Recorder::new(), 3)), None);
+        assert_eq!(a.first_difference(&run(Recorder::new(), 5)), Some(3));
+        assert_eq!(run(Recorder::new(), 1).first_difference(&a), Some(1));
+
+        let mut untraced = NoTrace;
+        untraced.emit(|| panic!("events aren't built for NoTrace"));
+        let mut rec = Recorder::new();
+        run(&mut rec, 1);
+        assert_eq!(rec.len(), 2);
+    }
+}